@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        Protocol,
+        Restaurant,
+        Brand,
+        StaffMember,
+    },
+    errors::{SetupError, ProtocolError},
+};
+
+// Shared authorization check for every restaurant instruction: the restaurant owner can do everything,
+// anyone else needs an active StaffMember record of this restaurant holding the required permission.
+pub fn authorize(
+    protocol: &Protocol,
+    restaurant: &Account<Restaurant>,
+    authority: &Pubkey,
+    staff_member: &Option<Account<StaffMember>>,
+    permission: u16,
+) -> Result<()> {
+    if *authority == restaurant.owner {
+        return Ok(());
+    }
+
+    let staff_member = staff_member.as_ref().ok_or(SetupError::Unauthorized)?;
+
+    require!(staff_member.publickey == *authority, SetupError::Unauthorized);
+    require!(staff_member.restaurant == restaurant.key(), SetupError::Unauthorized);
+    require!(staff_member.has_permission(permission), SetupError::Unauthorized);
+    require!(staff_member.is_active(), ProtocolError::StaffSuspended);
+    protocol.require_active(staff_member.initialized)?;
+
+    Ok(())
+}
+
+// Authorization for menu items and rewards: passing the brand of the restaurant (signed by the brand owner)
+// manages the brand-wide catalog, otherwise it falls back to `authorize`. Returns the key the items belong to.
+pub fn authorize_catalog(
+    protocol: &Protocol,
+    restaurant: &Account<Restaurant>,
+    brand: &Option<Account<Brand>>,
+    authority: &Pubkey,
+    staff_member: &Option<Account<StaffMember>>,
+    permission: u16,
+) -> Result<Pubkey> {
+    if let Some(brand) = brand {
+        require!(restaurant.brand == Some(brand.key()), SetupError::Unauthorized);
+        require!(brand.owner == *authority, SetupError::Unauthorized);
+
+        return Ok(brand.key());
+    }
+
+    authorize(protocol, restaurant, authority, staff_member, permission)?;
+
+    Ok(restaurant.key())
+}
@@ -10,4 +10,87 @@ pub mod admin_wallet {
     declare_id!("ADM12HQ5G2EzSwWy2nN1xXMyGjaBULuuX9GTgW2FPwZK");
 }
 
-pub const ED25519_PROGRAM_ID: &str = "Ed25519SigVerify111111111111111111111111111";
\ No newline at end of file
+pub const ED25519_PROGRAM_ID: &str = "Ed25519SigVerify111111111111111111111111111";
+
+pub const MAX_AUTHORITIES: usize = 10;
+
+pub const DEFAULT_ADMIN_COOLDOWN: i64 = 12 * 60 * 60;
+
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+// Max size of the Token-2022 group of a restaurant collection mint, i.e. how many memberships it can issue
+pub const MAX_MEMBERSHIPS: u32 = 1_000_000;
+
+// Loyalty defaults and limits, see LoyaltyConfig
+pub const DEFAULT_POINTS_PER_UNIT: u64 = 10;
+pub const MAX_CATEGORY_MULTIPLIERS: usize = 10;
+pub const MAX_PROMOTIONS: usize = 5;
+
+// Schedule limits, dayparts are referenced by index from the MenuItem.dayparts bitmask
+pub const MAX_OPENING_WINDOWS: usize = 14;
+pub const MAX_CLOSED_DATES: usize = 32;
+pub const MAX_DAYPARTS: usize = 8;
+
+// Dine-in tables
+pub const MAX_TABLE_LABEL_LEN: usize = 32;
+pub const MAX_TABLE_ORDERS: usize = 16;
+pub const TABLE_FREE: u8 = 0;
+pub const TABLE_SEATED: u8 = 1;
+pub const TABLE_DIRTY: u8 = 2;
+
+// Tax
+pub const MAX_TAX_RATES: usize = 16;
+
+// Recipes
+pub const MAX_RECIPE_LINES: usize = 16;
+
+// Inventory
+pub const MAX_LOW_STOCK_ITEMS: usize = 64; // Items of a restaurant that can have a par level, the LowStockIndex has room for all of them
+
+// Units of measure stored in Quantity.unit
+pub const UNIT_EACH: u8 = 0;
+pub const UNIT_GRAM: u8 = 1;
+pub const UNIT_MILLILITRE: u8 = 2;
+
+// Longest shift a manager can correct a shift to
+pub const MAX_CORRECTED_SHIFT_SECONDS: i64 = 16 * 60 * 60;
+
+// Tip pool splits
+pub const TIP_SPLIT_HOURS: u8 = 0;
+pub const TIP_SPLIT_POINTS: u8 = 1;
+
+// Order statuses stored in CustomerOrder.status
+pub const ORDER_PENDING: u8 = 0;
+pub const ORDER_COMPLETED: u8 = 1;
+pub const ORDER_FINALIZED: u8 = 2;
+pub const ORDER_CANCELLED: u8 = 4;
+pub const ORDER_READY: u8 = 5; // prepared by the kitchen, waiting to be handed off
+
+// Staff roles stored in StaffMember.role, they limit which order transitions the staff member can make
+pub const ROLE_NONE: u8 = 0; // only the permissions apply
+pub const ROLE_CASHIER: u8 = 1;
+pub const ROLE_KITCHEN: u8 = 2;
+pub const ROLE_EXPO: u8 = 3;
+pub const ROLE_MANAGER: u8 = 4;
+
+// Staff statuses stored in StaffMember.status, terminated staff only leave a StaffTombstone behind
+pub const STAFF_ACTIVE: u8 = 0;
+pub const STAFF_SUSPENDED: u8 = 1;
+
+// Pause flags stored in Protocol.paused, each one freezes a family of instructions
+pub const PAUSE_ORDERS: u8 = 1 << 0;
+pub const PAUSE_REWARDS: u8 = 1 << 1;
+pub const PAUSE_CUSTOMERS: u8 = 1 << 2;
+pub const PAUSE_INVENTORY: u8 = 1 << 3; // inventory and menu items
+pub const PAUSE_STAFF: u8 = 1 << 4;
+
+// Permissions stored in StaffMember.permissions, the restaurant owner implicitly holds all of them
+pub const PERMISSION_MANAGE_MENU: u16 = 1 << 0; // menu items and rewards
+pub const PERMISSION_MANAGE_INVENTORY: u16 = 1 << 1;
+pub const PERMISSION_UPDATE_ORDERS: u16 = 1 << 2; // order status and customer memberships
+pub const PERMISSION_ISSUE_REFUNDS: u16 = 1 << 3;
+pub const PERMISSION_AIRDROP_REWARDS: u16 = 1 << 4;
+pub const PERMISSION_MANAGE_STAFF: u16 = 1 << 5;
+pub const PERMISSION_MANAGE_RESTAURANT: u16 = 1 << 6; // restaurant profile
+pub const PERMISSION_RUN_PAYROLL: u16 = 1 << 7; // pays wages out of the restaurant vault
+pub const PERMISSION_ALL: u16 = (1 << 8) - 1;
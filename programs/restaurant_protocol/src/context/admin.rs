@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
 use crate::{
     state::{
+        Versioned,
         Admin,
         Protocol,
-        RestaurantAdmin
+        Proposal,
+        ProposalAction,
     },
-    constant,
     errors::{SetupError, ProtocolError},
 };
 
@@ -20,20 +21,31 @@ impl<'info> AdminInit<'info> {
             Create a new Admin Ix:
 
             Some security check:
-            - Check if the account that is initializing the admin is the admin itself, if not 
-            it should be the mutlisig account that is the admin of the enitre protocol.
+            - The signer must be a protocol authority executing an approved AddAdmin proposal, existing
+            admins can't create admins on their own.
             - Save the Time of initialization to render it useless for the first 12h of initialization.
 
             What the Instruction does:
             - Initialize the new admin account with the username (so we can monitor who are the admin
             account atm in an easy way) and the publickey of the new admin.
+            - Marks the AddAdmin proposal as executed.
 
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.admin_state.is_some() || self.admin.key() == constant::multisig_wallet::id(), SetupError::Unauthorized);
+
+        require!(self.protocol.is_authority(self.admin.key), SetupError::Unauthorized);
+        require!(!self.proposal.executed, SetupError::ProposalExecuted);
+        require!(self.proposal.is_approved(&self.protocol), SetupError::ProposalNotApproved);
+        require!(
+            self.proposal.action == ProposalAction::AddAdmin { admin: self.new_admin.key() },
+            SetupError::ProposalActionMismatch
+        );
+
+        self.proposal.executed = true;
         
         self.new_admin_state.set_inner(Admin {
+            version: Admin::VERSION,
             publickey: self.new_admin.key(),
             username,
             initialized: Clock::get()?.unix_timestamp,
@@ -53,78 +65,25 @@ impl<'info> AdminRemove<'info> {
             Remove Admin Ix:
 
             Some security check:
-            - Check if the account signing is the primary admin from the multisig wallet.
-
-            What the Instruction does:
-            - Closes the Admin_State account which is necessary for Admin rights, this is intended to only be used when the admin is compromised.
-            - Returns any account rent of the Admin_State account to the multisig wallet.   
-
-        */
-        
-        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.primary_admin.key() == constant::multisig_wallet::id(), SetupError::Unauthorized);
-    
-        
-        Ok(())
-    }
-}
-
-impl<'info> RestaurantAdminInit<'info> {
-    pub fn initialize_admin(
-        &mut self,
-        username: String,
-    ) -> Result<()> {
-
-        /*
-        
-            Create a new Admin Ix:
-
-            Some security check:
-            - Check if the account that is initializing the admin is the admin itself, if not 
-            it should be the mutlisig account that is the admin of the enitre protocol.
-            - Save the Time of initialization to render it useless for the first 12h of initialization.
-
-            What the Instruction does:
-            - Initialize the new admin account with the username (so we can monitor who are the admin
-            account atm in an easy way) and the publickey of the new admin.
-
-        */
-        
-        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_owner.key() != self.restaurant.owner.key(), SetupError::Unauthorized);
-        
-        self.restaurant_admin_state.set_inner(RestaurantAdmin {
-            publickey: self.restaurant_admin.key(),
-            restaurant: *self.restaurant.key,
-            username,
-            initialized: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
-    }
-}
-
-impl<'info> RestaurantAdminRemove<'info> {
-    pub fn remove_admin(
-        &mut self
-    ) -> Result<()> {
-
-        /*
-        
-            Remove Admin Ix:
-
-            Some security check:
-            - Check if the account signing is the primary admin from the multisig wallet.
+            - Check if the account signing is a protocol authority executing an approved RemoveAdmin proposal.
 
             What the Instruction does:
             - Closes the Admin_State account which is necessary for Admin rights, this is intended to only be used when the admin is compromised.
-            - Returns any account rent of the Admin_State account to the multisig wallet.   
+            - Returns any account rent of the Admin_State account to the executing authority.
+            - Marks the RemoveAdmin proposal as executed.
 
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_owner.key() == self.restaurant.owner.key(), SetupError::Unauthorized);
-    
+        require!(self.protocol.is_authority(self.primary_admin.key), SetupError::Unauthorized);
+        require!(!self.proposal.executed, SetupError::ProposalExecuted);
+        require!(self.proposal.is_approved(&self.protocol), SetupError::ProposalNotApproved);
+        require!(
+            self.proposal.action == ProposalAction::RemoveAdmin { admin: self.admin.key() },
+            SetupError::ProposalActionMismatch
+        );
+
+        self.proposal.executed = true;
         
         Ok(())
     }
@@ -135,11 +94,6 @@ impl<'info> RestaurantAdminRemove<'info> {
 pub struct AdminInit<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
-    #[account(
-        seeds = [b"admin_state", admin.key().as_ref()],
-        bump
-    )]
-    pub admin_state: Option<Account<'info, Admin>>,
     #[account(mut)]
     /// CHECK: this is ok because admin is setting up on owner behalf
     pub new_admin: AccountInfo<'info>,
@@ -151,6 +105,12 @@ pub struct AdminInit<'info> {
         bump
     )]
     pub new_admin_state: Account<'info, Admin>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
     #[account(
         seeds = [b"protocol"],
         bump,
@@ -171,67 +131,18 @@ pub struct AdminRemove<'info> {
         bump
     )]
     pub admin_state: Account<'info, Admin>,
-    pub primary_admin: Signer<'info>,
-    #[account(
-        seeds = [b"protocol"],
-        bump,
-    )]
-    pub protocol: Account<'info, Protocol>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-#[instruction(username: String)]
-pub struct RestaurantAdminInit<'info> {
-    #[account(mut)]
-    pub restaurant_owner: Signer<'info>,
     #[account(mut)]
-    /// CHECK: this is ok because we are checking the seeds and bump in the program
-    pub restaurant: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: this is ok because we are checking the seeds and bump in the program
-    pub restaurant_admin: AccountInfo<'info>,
-    #[account(
-        init,
-        payer = restaurant_owner,
-        space = Admin::INIT_SPACE + 5,
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
-        bump
-    )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
-    #[account(
-        seeds = [b"protocol"],
-        bump,
-    )]
-    pub protocol: Account<'info, Protocol>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct RestaurantAdminRemove<'info> {
-    #[account(mut)]
-    pub restaurant_owner: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"restaurant", restaurant_owner.key().as_ref()],
-        bump
-    )]
-    /// CHECK: this is ok because we are checking the seeds and bump in the program
-    pub restaurant: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: this is ok because we are checking the seeds and bump in the program
-    pub restaurant_admin: AccountInfo<'info>,
+    pub primary_admin: Signer<'info>,
     #[account(
         mut,
-        close = restaurant_owner,
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub proposal: Account<'info, Proposal>,
     #[account(
         seeds = [b"protocol"],
         bump,
     )]
     pub protocol: Account<'info, Protocol>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
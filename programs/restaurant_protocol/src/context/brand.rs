@@ -0,0 +1,374 @@
+use anchor_lang::{
+    solana_program::{
+        sysvar::rent::ID as RENT_ID,
+        program::{invoke, invoke_signed}
+    },
+    prelude::*,
+};
+pub use anchor_spl::token_2022::Token2022;
+pub use spl_token_2022::{
+    extension::ExtensionType,
+    extension::group_pointer::instruction::initialize as initialize_group_pointer,
+    extension::metadata_pointer::instruction::initialize as initialize_metadata_pointer,
+    instruction::initialize_mint2,
+};
+pub use spl_token_group_interface::instruction::initialize_group;
+pub use spl_token_metadata_interface::{
+    state::TokenMetadata,
+    instruction::initialize as initialize_metadata_account,
+};
+use crate::{
+    state::{
+        Versioned,
+        Brand,
+        Restaurant,
+        Protocol,
+    },
+    constant::MAX_MEMBERSHIPS,
+    errors::ProtocolError,
+};
+
+impl<'info> BrandInit<'info> {
+    pub fn add(
+        &mut self,
+        name: String,
+    ) -> Result<()> {
+
+        /*
+        
+            Create Brand Ix:
+
+            Some security check:
+            - The signer becomes the brand owner.
+
+            What these Instructions do:
+            - Creates a Brand that restaurant locations can join to share memberships, points and a catalog.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+
+        self.brand.set_inner(Brand {
+            version: Brand::VERSION,
+            owner: self.owner.key(),
+            name,
+            location_count: 0,
+            customer_count: 0,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> BrandCollectionInit<'info> {
+    pub fn init_collection(
+        &mut self,
+        symbol: String,
+        uri: String,
+        bumps: BrandCollectionInitBumps,
+    ) -> Result<()> {
+
+        /*
+        
+            Create Brand Collection Ix:
+
+            Some security check:
+            - The signer must be the brand owner.
+
+            What these Instructions do:
+            - Creates the brand Collection mint, a Token-2022 group the memberships issued at every location of
+            the brand are members of. Locations can't issue memberships before it exists.
+            - The auth PDA is the mint and group update authority of the collection.
+            - Stores the brand name, the symbol and the uri as the Token-2022 metadata of the collection mint.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+
+        let size = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &[
+                ExtensionType::GroupPointer,
+                ExtensionType::MetadataPointer,
+            ],
+        ).unwrap();
+
+        let metadata = TokenMetadata {
+            update_authority: spl_pod::optional_keys::OptionalNonZeroPubkey::try_from(Some(self.auth.key())).unwrap(),
+            mint: self.mint.key(),
+            name: self.brand.name.to_string(),
+            symbol,
+            uri,
+            additional_metadata: vec![],
+        };
+
+        // the group and metadata are allocated by the token program, the mint only has to be funded for them
+        let group_size = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &[
+                ExtensionType::GroupPointer,
+                ExtensionType::MetadataPointer,
+                ExtensionType::TokenGroup,
+            ],
+        ).unwrap();
+
+        let extension_extra_space = metadata.tlv_size_of().unwrap();
+        let rent = &Rent::from_account_info(&self.rent.to_account_info())?;
+        let lamports = rent.minimum_balance(group_size + extension_extra_space);
+
+        let collection_key = self.brand.key();
+        let seeds: &[&[u8]; 3] = &[
+            b"mint",
+            collection_key.as_ref(),
+            &[bumps.mint],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        invoke_signed(
+            &solana_program::system_instruction::create_account(
+                &self.owner.key(),
+                &self.mint.key(),
+                lamports,
+                (size).try_into().unwrap(),
+                &spl_token_2022::id(),
+            ),
+            &[
+                self.owner.to_account_info(),
+                self.mint.to_account_info(),
+            ],
+            signer_seeds
+        )?;
+
+        invoke(
+            &initialize_group_pointer(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                Some(self.auth.key()),
+                Some(self.mint.key()),
+            )?,
+            &[
+                self.mint.to_account_info(),
+            ],  
+        )?;
+
+        invoke(
+            &initialize_metadata_pointer(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                Some(self.auth.key()),
+                Some(self.mint.key()),
+            )?,
+            &[
+                self.mint.to_account_info(),
+            ],
+        )?;
+
+        invoke(
+            &initialize_mint2(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                None,
+                0,
+            )?,
+            &[
+                self.mint.to_account_info(),
+            ],
+        )?;
+
+        let seeds: &[&[u8]; 2] = &[
+            b"auth",
+            &[bumps.auth],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        invoke_signed(
+            &initialize_metadata_account(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                metadata.name,
+                metadata.symbol,
+                metadata.uri,
+            ),
+            &[
+                self.mint.to_account_info(),
+                self.auth.to_account_info(),
+            ],
+            signer_seeds
+        )?;
+
+        invoke_signed(
+            &initialize_group(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                Some(self.auth.key()),
+                MAX_MEMBERSHIPS,
+            ),
+            &[
+                self.mint.to_account_info(),
+                self.auth.to_account_info(),
+            ],
+            signer_seeds
+        )?;
+
+        Ok(())
+    }
+}
+
+impl<'info> BrandLocationAdd<'info> {
+    pub fn add_location(&mut self) -> Result<()> {
+
+        /*
+        
+            Add Brand Location Ix:
+
+            Some security check:
+            - Both the brand owner and the restaurant owner have to sign.
+            - The restaurant can't be in a brand yet or have customers, their memberships are keyed by the
+            restaurant and would be stranded once memberships move to the brand.
+
+            What these Instructions do:
+            - Adds the restaurant to the brand.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        require!(self.restaurant.brand.is_none(), ProtocolError::InvalidBrand);
+        require!(self.restaurant.customer_count == 0, ProtocolError::InvalidBrand);
+
+        self.restaurant.brand = Some(self.brand.key());
+        self.brand.location_count = self.brand.location_count.checked_add(1).ok_or(ProtocolError::CountOverflow)?;
+
+        Ok(())
+    }
+}
+
+impl<'info> BrandLocationRemove<'info> {
+    pub fn remove_location(&mut self) -> Result<()> {
+
+        /*
+        
+            Remove Brand Location Ix:
+
+            Some security check:
+            - The signer must be the brand owner or the restaurant owner.
+
+            What these Instructions do:
+            - Takes the restaurant out of the brand, it goes back to its own memberships and catalog. The memberships
+            it issued for the brand stay with the brand.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        require!(
+            self.signer.key() == self.brand.owner || self.signer.key() == self.restaurant.owner,
+            ProtocolError::UnauthorizedAdmin
+        );
+        require!(self.restaurant.brand == Some(self.brand.key()), ProtocolError::InvalidBrand);
+
+        self.restaurant.brand = None;
+        self.brand.location_count = self.brand.location_count.checked_sub(1).ok_or(ProtocolError::CountOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct BrandInit<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = Brand::INIT_SPACE + name.len(),
+        seeds = [b"brand", owner.key().as_ref()],
+        bump
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BrandCollectionInit<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"brand", owner.key().as_ref()],
+        bump
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        mut,
+        seeds = [b"mint", brand.key().as_ref()],
+        bump
+    )]
+    /// CHECK: the brand collection mint, created and initialized through the token program
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"auth"],
+        bump
+    )]
+    /// CHECK: mint and group update authority of the collection mint
+    pub auth: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    #[account(address = RENT_ID)]
+    /// CHECK: this is fine since we are hard coding the rent sysvar.
+    pub rent: UncheckedAccount<'info>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BrandLocationAdd<'info> {
+    pub brand_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"brand", brand_owner.key().as_ref()],
+        bump
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(address = restaurant.owner)]
+    pub restaurant_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
+        bump,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct BrandLocationRemove<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"brand", brand.owner.as_ref()],
+        bump
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
+        bump,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
@@ -0,0 +1,85 @@
+use {
+    anchor_lang::prelude::*,
+    anchor_spl::token::Mint,
+};
+use crate::{
+    state::{
+        Versioned,
+        Protocol,
+        Proposal,
+        ProposalAction,
+        CurrencyListing,
+    },
+    errors::SetupError,
+};
+
+impl<'info> CurrencyList<'info> {
+    pub fn list(
+        &mut self,
+    ) -> Result<()> {
+
+        /*
+        
+            List Currency Ix:
+
+            Some security check:
+            - Check if the account signing is a protocol authority executing an approved ListCurrency proposal.
+
+            What the Instruction does:
+            - Creates (or updates) the allowlist entry of the payment mint with its decimals.
+            - Restaurants can only be created with, and orders can only be paid in, listed mints.
+            - Marks the ListCurrency proposal as executed.
+
+        */
+
+        require!(self.protocol.is_authority(self.authority.key), SetupError::Unauthorized);
+        require!(!self.proposal.executed, SetupError::ProposalExecuted);
+        require!(self.proposal.is_approved(&self.protocol), SetupError::ProposalNotApproved);
+
+        let listed = match self.proposal.action {
+            ProposalAction::ListCurrency { mint, listed } => {
+                require!(mint == self.mint.key(), SetupError::ProposalActionMismatch);
+                listed
+            }
+            _ => return err!(SetupError::ProposalActionMismatch),
+        };
+
+        self.currency_listing.set_inner(CurrencyListing {
+            version: CurrencyListing::VERSION,
+            mint: self.mint.key(),
+            decimals: self.mint.decimals,
+            listed,
+        });
+
+        self.proposal.executed = true;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CurrencyList<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CurrencyListing::INIT_SPACE,
+        seeds = [b"currency", mint.key().as_ref()],
+        bump
+    )]
+    pub currency_listing: Account<'info, CurrencyListing>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,11 +1,16 @@
 use crate::{
+    auth::authorize,
     state::{
+        StaffMember,
+        Versioned,
         Restaurant,
+        Brand,
         Customer,
         Protocol,
         CustomerNft,
         Attributes
     },
+    constant::{PAUSE_CUSTOMERS, PERMISSION_UPDATE_ORDERS},
     errors::ProtocolError,
 };
 
@@ -29,10 +34,11 @@ pub use anchor_spl::{
 };
 pub use spl_token_2022::{
     extension::ExtensionType,
-    instruction::initialize_mint2,
+    instruction::{initialize_mint2, initialize_permanent_delegate},
     extension::metadata_pointer::instruction::initialize as initialize_metadata_pointer,
     extension::group_member_pointer::instruction::initialize as initialize_group_member_pointer,
 };
+pub use spl_token_group_interface::instruction::initialize_member;
 pub use spl_token_metadata_interface::{
     state::{TokenMetadata, Field},
     instruction::{initialize as initialize_metadata_account, update_field as update_metadata_account},
@@ -58,15 +64,24 @@ impl<'info> CustomerInit<'info> {
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_CUSTOMERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_UPDATE_ORDERS)?;
+        // memberships of a brand location are issued by the brand
+        require!(self.brand.as_ref().map(|brand| brand.key()) == self.restaurant.brand, ProtocolError::InvalidBrand);
         
         self.customer_profile.set_inner(Customer {
+            version: Customer::VERSION,
             id,
-            restaurant: self.restaurant.key(),
+            issuer: self.restaurant.brand.unwrap_or(self.restaurant.key()),
             publickey: self.customer.key(),
             customer_nft: self.mint.key(),
             member_since: Clock::get()?.unix_timestamp,
         });
 
+        self.customer_nft.version = CustomerNft::VERSION;
+        self.customer_nft.id = id;
+
         // Step 1: Initialize Account
         let size = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
             &[
@@ -110,15 +125,25 @@ impl<'info> CustomerInit<'info> {
         let metadata = TokenMetadata {
             update_authority: spl_pod::optional_keys::OptionalNonZeroPubkey::try_from(Some(self.auth.key())).unwrap(),
             mint: self.mint.key(),
-            name: self.restaurant.name.to_string() + " Customer Membership",
+            name: self.brand.as_ref().map_or(&self.restaurant.name, |brand| &brand.name).to_string() + " Customer Membership",
             symbol: self.restaurant.symbol.to_string(),
             uri,
             additional_metadata: attributes.into_iter().map(|attr| (attr.key, attr.value)).collect(),
         };
 
+        // the group member and metadata are allocated by the token program, the mint only has to be funded for them
+        let member_size = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &[
+                ExtensionType::GroupMemberPointer,
+                ExtensionType::PermanentDelegate,
+                ExtensionType::MetadataPointer,
+                ExtensionType::TokenGroupMember,
+            ],
+        ).unwrap();
+
         let extension_extra_space = metadata.tlv_size_of().unwrap();
         let rent = &Rent::from_account_info(&self.rent.to_account_info())?;
-        let lamports = rent.minimum_balance(size + extension_extra_space);
+        let lamports = rent.minimum_balance(member_size + extension_extra_space);
 
         let nft_key = self.customer_nft.key();
         let seeds: &[&[u8]; 3] = &[
@@ -130,28 +155,40 @@ impl<'info> CustomerInit<'info> {
 
         invoke_signed(
             &solana_program::system_instruction::create_account(
-                &self.restaurant_admin.key(),
+                &self.authority.key(),
                 &self.mint.key(),
                 lamports,
                 (size).try_into().unwrap(),
                 &spl_token_2022::id(),
             ),
-            &vec![
-                self.restaurant_admin.to_account_info(),
+            &[
+                self.authority.to_account_info(),
                 self.mint.to_account_info(),
             ],
             signer_seeds
         )?;
 
+        // 2.2: Permanent Delegate, lets the program revoke a membership
+        invoke(
+            &initialize_permanent_delegate(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+            )?,
+            &[
+                self.mint.to_account_info(),
+            ],
+        )?;
+
          // 2.3: Add group member pointer
          invoke(
             &initialize_group_member_pointer(
                 &self.token_2022_program.key(),
                 &self.mint.key(),
                 Some(self.auth.key()),
-                Some(self.restaurant.key()), 
+                Some(self.mint.key()), 
             )?,
-            &vec![
+            &[
                 self.mint.to_account_info(),
             ],  
         )?;
@@ -164,7 +201,7 @@ impl<'info> CustomerInit<'info> {
             Some(self.auth.key()),
             Some(self.mint.key()),
             )?,
-            &vec![
+            &[
             self.mint.to_account_info(),
             ],
         )?;
@@ -178,7 +215,7 @@ impl<'info> CustomerInit<'info> {
                 None,
                 0,
             )?,
-            &vec![
+            &[
                 self.mint.to_account_info(),
             ],
             signer_seeds
@@ -201,7 +238,7 @@ impl<'info> CustomerInit<'info> {
                 metadata.symbol,
                 metadata.uri,
             ),
-            &vec![
+            &[
                 self.mint.to_account_info(),
                 self.auth.to_account_info(),
             ],
@@ -217,7 +254,7 @@ impl<'info> CustomerInit<'info> {
                     Field::Key(field),
                     value,
                 ),
-                &vec![
+                &[
                     self.mint.to_account_info(),
                     self.auth.to_account_info(),
                 ],
@@ -225,13 +262,31 @@ impl<'info> CustomerInit<'info> {
             )?;
         }
 
+        // the membership is a member of the brand or restaurant collection group
+        invoke_signed(
+            &initialize_member(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                &self.collection_mint.key(),
+                &self.auth.key(),
+            ),
+            &[
+                self.mint.to_account_info(),
+                self.auth.to_account_info(),
+                self.collection_mint.to_account_info(),
+            ],
+            signer_seeds
+        )?;
+
         // Initialize ATA if it doesn't exist
         if self.customer_mint_ata.owner != &self.customer.key() {
             create(
             CpiContext::new(
                 self.token_2022_program.to_account_info(),
                 Create {
-                payer: self.restaurant_admin.to_account_info(), // payer
+                payer: self.authority.to_account_info(), // payer
                 associated_token: self.customer_mint_ata.to_account_info(),
                 authority: self.customer.to_account_info(), // owner
                 mint: self.mint.to_account_info(),
@@ -293,7 +348,10 @@ impl<'info> CustomerInit<'info> {
             require!(_after_state.base.amount == 1, ProtocolError::InvalidBalancePostMint);
         }
 
-        self.restaurant.customer_count += 1;
+        match self.brand.as_mut() {
+            Some(brand) => brand.customer_count = brand.customer_count.checked_add(1).ok_or(ProtocolError::CountOverflow)?,
+            None => self.restaurant.customer_count = self.restaurant.customer_count.checked_add(1).ok_or(ProtocolError::CountOverflow)?,
+        }
 
         Ok(())
     }
@@ -303,23 +361,28 @@ impl<'info> CustomerInit<'info> {
 #[instruction(id: u64, uri: String, attributes: Vec<Attributes>)]
 pub struct CustomerInit<'info> {
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
     /// CHECK: This is ok, we are creating everything on the customer behalf
     pub customer: AccountInfo<'info>,
     #[account(
         init,
-        payer = restaurant_admin,
-        space = Customer::INIT_SPACE + 5,
-        seeds = [b"customer", customer.key().as_ref()],
+        payer = authority,
+        space = 8 + Customer::INIT_SPACE,
+        seeds = [b"customer", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump
     )]
     pub customer_profile: Account<'info, Customer>,
     #[account(
         init,
-        payer = restaurant_admin,
-        seeds = [b"member_nft", customer.key().as_ref(), restaurant.key().as_ref()],
+        payer = authority,
+        seeds = [b"member_nft", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump,
-        space = CustomerNft::INIT_SPACE + attributes.iter().map(|attr| attr.key.len() + attr.value.len()).sum::<usize>(),
+        space = 8 + CustomerNft::INIT_SPACE,
     )] 
     pub customer_nft: Account<'info, CustomerNft>,
     #[account(
@@ -329,6 +392,13 @@ pub struct CustomerInit<'info> {
     )]
     /// CHECK
     pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint", restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
+        bump
+    )]
+    /// CHECK: the brand or restaurant collection mint, its group member counter is incremented by the token program
+    pub collection_mint: UncheckedAccount<'info>,
     #[account(
         seeds = [b"auth"],
         bump
@@ -348,13 +418,19 @@ pub struct CustomerInit<'info> {
     /// CHECK
     pub customer_mint_ata: AccountInfo<'info>,
     /// CHECK: this is ok because admin is setting up on owner behalf
-    #[account(mut)]
+    #[account(
+        mut,
+        address = restaurant.owner,
+    )]
     pub restaurant_owner: AccountInfo<'info>,
     #[account(
-        seeds = [b"restaurant", restaurant_owner.key().as_ref()],
+        mut,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
         bump,
     )] 
     pub restaurant: Account<'info, Restaurant>,
+    #[account(mut)]
+    pub brand: Option<Account<'info, Brand>>,
     #[account(
         seeds = [b"protocol"],
         bump,
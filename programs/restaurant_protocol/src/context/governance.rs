@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        Versioned,
+        Protocol,
+        Proposal,
+        ProposalAction,
+    },
+    constant::MAX_FEE_BPS,
+    errors::{SetupError, ProtocolError},
+};
+
+impl<'info> ProposalCreate<'info> {
+    pub fn propose(
+        &mut self,
+        action: ProposalAction,
+    ) -> Result<()> {
+
+        /*
+        
+            Create Proposal Ix:
+
+            Some security check:
+            - Check if the account signing is part of the protocol authority set.
+
+            What the Instruction does:
+            - Creates a new proposal for a sensitive action (lock toggle, admin add/remove, config changes).
+            - The proposer counts as the first approval.
+
+        */
+
+        require!(self.protocol.is_authority(self.authority.key), SetupError::Unauthorized);
+
+        if let ProposalAction::SetAuthorities { authorities, threshold } = &action {
+            // validate upfront so a bad authority set can't be approved
+            Protocol::validate_authorities(authorities, *threshold)?;
+        }
+
+        if let ProposalAction::SetAdminCooldown { cooldown } = &action {
+            require!(*cooldown >= 0, ProtocolError::InvalidCooldown);
+        }
+
+        if let ProposalAction::SetProtocolFee { fee_bps } = &action {
+            require!(*fee_bps <= MAX_FEE_BPS, ProtocolError::InvalidFee);
+        }
+
+        self.proposal.set_inner(Proposal {
+            version: Proposal::VERSION,
+            id: self.protocol.proposal_count,
+            proposer: self.authority.key(),
+            action,
+            approvals: vec![self.authority.key()],
+            executed: false,
+            created_at: Clock::get()?.unix_timestamp,
+        });
+
+        self.protocol.proposal_count += 1;
+
+        Ok(())
+    }
+}
+
+impl<'info> ProposalApprove<'info> {
+    pub fn approve(
+        &mut self,
+    ) -> Result<()> {
+
+        /*
+        
+            Approve Proposal Ix:
+
+            Some security check:
+            - Check if the account signing is part of the protocol authority set.
+            - Check that the proposal is still pending and wasn't approved by the signer already.
+
+            What the Instruction does:
+            - Adds the signer to the approvals of the proposal.
+
+        */
+
+        require!(self.protocol.is_authority(self.authority.key), SetupError::Unauthorized);
+        require!(!self.proposal.executed, SetupError::ProposalExecuted);
+        require!(!self.proposal.approvals.contains(self.authority.key), SetupError::ProposalAlreadyApproved);
+
+        self.proposal.approvals.push(self.authority.key());
+
+        Ok(())
+    }
+}
+
+impl<'info> ProposalExecute<'info> {
+    pub fn execute(
+        &mut self,
+    ) -> Result<()> {
+
+        /*
+        
+            Execute Proposal Ix:
+
+            Some security check:
+            - Check if the account signing is part of the protocol authority set.
+            - Check that the proposal is still pending and reached the approval threshold.
+
+            What the Instruction does:
+            - Applies the action of the proposal to the Protocol account.
+            - Admin add/remove, treasury withdraw and currency listing proposals need extra accounts, so they are
+            executed through initialize_admin_account / remove_admin_account / withdraw_treasury / list_currency instead.
+
+        */
+
+        require!(self.protocol.is_authority(self.authority.key), SetupError::Unauthorized);
+        require!(!self.proposal.executed, SetupError::ProposalExecuted);
+        require!(self.proposal.is_approved(&self.protocol), SetupError::ProposalNotApproved);
+
+        match self.proposal.action.clone() {
+            ProposalAction::ToggleLock => {
+                self.protocol.locked = !self.protocol.locked;
+            }
+            ProposalAction::SetAuthorities { authorities, threshold } => {
+                self.protocol.set_authorities(authorities, threshold)?;
+            }
+            ProposalAction::SetAdminCooldown { cooldown } => {
+                self.protocol.admin_cooldown = cooldown;
+            }
+            ProposalAction::SetProtocolFee { fee_bps } => {
+                self.protocol.fee_bps = fee_bps;
+            }
+            ProposalAction::SetPauseFlags { flags } => {
+                self.protocol.paused = flags;
+            }
+            ProposalAction::AddAdmin { .. }
+            | ProposalAction::RemoveAdmin { .. }
+            | ProposalAction::WithdrawTreasury { .. }
+            | ProposalAction::ListCurrency { .. } => {
+                return err!(SetupError::ProposalActionMismatch);
+            }
+        }
+
+        self.proposal.executed = true;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ProposalCreate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = Proposal::INIT_SPACE,
+        seeds = [b"proposal", protocol.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposalApprove<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct ProposalExecute<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
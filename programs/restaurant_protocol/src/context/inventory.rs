@@ -1,12 +1,19 @@
 use anchor_lang::prelude::*;
 use crate::{
+    auth::authorize,
+    events::LowStock,
     state::{
-        RestaurantAdmin,
+        Versioned,
+        Restaurant,
+        StaffMember,
         InventoryItem,
+        LowStockIndex,
+        Money,
+        Quantity,
         Protocol
     },
-    constant,
-    errors::{SetupError, ProtocolError},
+    constant::{PAUSE_INVENTORY, PERMISSION_MANAGE_INVENTORY},
+    errors::ProtocolError,
 };
 
 impl<'info> InventoryAdd<'info> {
@@ -15,8 +22,8 @@ impl<'info> InventoryAdd<'info> {
         sku: u64,
         category: Pubkey,
         name: String,
-        price: f64,
-        stock: f64,
+        price: Money,
+        stock: Quantity,
     ) -> Result<()> {
 
         /*
@@ -29,15 +36,22 @@ impl<'info> InventoryAdd<'info> {
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin_state.restaurant.key() == *self.restaurant.key, SetupError::Unauthorized);
+        self.protocol.require_unpaused(PAUSE_INVENTORY)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_INVENTORY)?;
+        stock.validate()?;
         
         self.inventory_state.set_inner(InventoryItem {
+            version: InventoryItem::VERSION,
+            restaurant: self.restaurant.key(),
             sku,
             category,
             name,
             price,
             stock,
             last_order: 0,
+            reorder_point: Quantity { amount: 0, unit: stock.unit },
+            par: Quantity { amount: 0, unit: stock.unit },
         });
 
         Ok(())
@@ -50,8 +64,8 @@ impl<'info> InventoryUpdate<'info> {
         sku: u64,
         category: Pubkey,
         name: String,
-        price: f64,
-        stock: f64,
+        price: Money,
+        stock: Quantity,
         last_order: u64
     ) -> Result<()> {
 
@@ -59,22 +73,109 @@ impl<'info> InventoryUpdate<'info> {
         
 
             Some security check:
-            
+            - A tracked item (with a par level) keeps the unit of measure of its thresholds.
+
+            What the Instruction does:
+            - Replaces the item, keeping its reorder point and par.
+            - Lists the item in the LowStockIndex while the stock is below par, emits LowStock when the new stock
+            drops below the reorder point.
 
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin_state.restaurant.key() == *self.restaurant.key, SetupError::Unauthorized);
+        self.protocol.require_unpaused(PAUSE_INVENTORY)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_INVENTORY)?;
+        stock.validate()?;
+
+        let was_low = self.inventory_state.is_low();
+        let (reorder_point, par) = if self.inventory_state.is_tracked() {
+            require!(stock.unit == self.inventory_state.par.unit, ProtocolError::UnitMismatch);
+            (self.inventory_state.reorder_point, self.inventory_state.par)
+        } else {
+            (Quantity { amount: 0, unit: stock.unit }, Quantity { amount: 0, unit: stock.unit })
+        };
         
         self.inventory_state.set_inner(InventoryItem {
+            version: InventoryItem::VERSION,
+            restaurant: self.restaurant.key(),
             sku,
             category,
             name,
             price,
             stock,
-            last_order
+            last_order,
+            reorder_point,
+            par,
         });
 
+        self.low_stock_index.refresh(self.inventory_state.key(), &self.inventory_state)?;
+        if !was_low && self.inventory_state.is_low() {
+            emit!(LowStock {
+                restaurant: self.restaurant.key(),
+                inventory: self.inventory_state.key(),
+                sku,
+                stock,
+                reorder_point,
+                par,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> InventoryThresholdsUpdate<'info> {
+    pub fn update(
+        &mut self,
+        reorder_point: Quantity,
+        par: Quantity,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Inventory Thresholds Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_MANAGE_INVENTORY.
+            - Both thresholds use the unit of measure of the stock and the reorder point can't be above par.
+            - At most MAX_LOW_STOCK_ITEMS items of the restaurant can have a par level, more fail with LowStockIndexFull.
+
+            What the Instruction does:
+            - Sets the reorder point and the par level (0 stops tracking the item) of the item.
+            - Lists the item in the LowStockIndex while the stock is below par, emits LowStock when the stock is now
+            below the reorder point.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_INVENTORY)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_INVENTORY)?;
+        let stock = self.inventory_state.stock;
+        require!(
+            reorder_point.unit == stock.unit && par.unit == stock.unit,
+            ProtocolError::UnitMismatch
+        );
+        require!(reorder_point.amount <= par.amount, ProtocolError::InvalidThresholds);
+
+        let was_low = self.inventory_state.is_low();
+        self.low_stock_index.track(self.inventory_state.is_tracked(), par.amount > 0)?;
+        self.inventory_state.reorder_point = reorder_point;
+        self.inventory_state.par = par;
+
+        self.low_stock_index.refresh(self.inventory_state.key(), &self.inventory_state)?;
+        if !was_low && self.inventory_state.is_low() {
+            emit!(LowStock {
+                restaurant: self.restaurant.key(),
+                inventory: self.inventory_state.key(),
+                sku: self.inventory_state.sku,
+                stock,
+                reorder_point,
+                par,
+            });
+        }
+
         Ok(())
     }
 }
@@ -98,31 +199,36 @@ impl<'info> InventoryRemove<'info> {
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin.key() == constant::multisig_wallet::id(), SetupError::Unauthorized);
-    
+        self.protocol.require_unpaused(PAUSE_INVENTORY)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_INVENTORY)?;
+
+        // a removed item is neither tracked nor low anymore
+        self.low_stock_index.track(self.inventory_state.is_tracked(), false)?;
+        let inventory = self.inventory_state.key();
+        self.low_stock_index.items.retain(|key| *key != inventory);
         
         Ok(())
     }
 }
 
 #[derive(Accounts)]
-#[instruction(username: String)]
+#[instruction(sku: u64, category: Pubkey, name: String)]
 pub struct InventoryAdd<'info> {
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     pub inventory_item: SystemAccount<'info>,
     #[account(
         init,
-        payer = restaurant_admin,
-        space = InventoryItem::INIT_SPACE + 5,
+        payer = authority,
+        space = InventoryItem::INIT_SPACE + name.len(),
         seeds = [b"inventory_state", inventory_item.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
@@ -138,21 +244,27 @@ pub struct InventoryAdd<'info> {
 #[derive(Accounts)]
 pub struct InventoryUpdate<'info> {
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     pub inventory_item: SystemAccount<'info>,
     #[account(
+        mut,
         seeds = [b"inventory_state", inventory_item.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
     pub inventory_state: Account<'info, InventoryItem>,
+    #[account(
+        mut,
+        seeds = [b"low_stock", restaurant.key().as_ref()],
+        bump
+    )]
+    pub low_stock_index: Account<'info, LowStockIndex>,
     #[account(
         seeds = [b"protocol"],
         bump,
@@ -161,21 +273,61 @@ pub struct InventoryUpdate<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct InventoryThresholdsUpdate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    pub inventory_item: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"inventory_state", inventory_item.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub inventory_state: Account<'info, InventoryItem>,
+    #[account(
+        mut,
+        seeds = [b"low_stock", restaurant.key().as_ref()],
+        bump
+    )]
+    pub low_stock_index: Account<'info, LowStockIndex>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
 #[derive(Accounts)]
 pub struct InventoryRemove<'info> {
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     pub inventory_item: SystemAccount<'info>,
     #[account(
         mut,
-        close = restaurant_admin,
+        close = authority,
         seeds = [b"inventory_state", inventory_item.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
     pub inventory_state: Account<'info, InventoryItem>,
+    #[account(
+        mut,
+        seeds = [b"low_stock", restaurant.key().as_ref()],
+        bump
+    )]
+    pub low_stock_index: Account<'info, LowStockIndex>,
     #[account(
         seeds = [b"protocol"],
         bump,
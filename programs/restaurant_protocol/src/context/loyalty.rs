@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::{
+    auth::authorize,
+    state::{
+        Restaurant,
+        StaffMember,
+        LoyaltyConfig,
+        CategoryMultiplier,
+        Promotion,
+        Protocol
+    },
+    constant::{PAUSE_REWARDS, PERMISSION_MANAGE_MENU},
+    errors::ProtocolError,
+};
+
+impl<'info> LoyaltyUpdate<'info> {
+    pub fn update(
+        &mut self,
+        points_per_unit: u64,
+        min_spend: u64,
+        category_multipliers: Vec<CategoryMultiplier>,
+        promotions: Vec<Promotion>,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Loyalty Config Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_MANAGE_MENU.
+            - The multipliers and promotions must fit in the account and be well formed.
+
+            What these Instructions do:
+            - Replaces the earning rules applied by add_order when crediting reward points.
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_REWARDS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_MENU)?;
+        LoyaltyConfig::validate(&category_multipliers, &promotions)?;
+
+        self.loyalty_config.points_per_unit = points_per_unit;
+        self.loyalty_config.min_spend = min_spend;
+        self.loyalty_config.category_multipliers = category_multipliers;
+        self.loyalty_config.promotions = promotions;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct LoyaltyUpdate<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        seeds = [b"loyalty", restaurant.key().as_ref()],
+        bump
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
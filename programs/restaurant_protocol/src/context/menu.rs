@@ -1,11 +1,17 @@
 use anchor_lang::prelude::*;
 use crate::{
+    auth::authorize_catalog,
     state::{
-        RestaurantAdmin,
+        Versioned,
+        Restaurant,
+        Brand,
+        StaffMember,
         MenuItem,
+        RecipeLine,
+        Money,
         Protocol
     },
-    constant,
+    constant::{PAUSE_INVENTORY, PERMISSION_MANAGE_MENU},
     errors::{SetupError, ProtocolError},
 };
 
@@ -15,8 +21,8 @@ impl<'info> MenuInit<'info> {
         sku: u64,
         category: Pubkey,
         name: String,
-        price: f64,
-        ingredients: Vec<String>,
+        price: Money,
+        recipe: Vec<RecipeLine>,
         active: bool,
     ) -> Result<()> {
 
@@ -30,15 +36,21 @@ impl<'info> MenuInit<'info> {
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin_state.restaurant.key() == *self.restaurant.key, SetupError::Unauthorized);
+        self.protocol.require_unpaused(PAUSE_INVENTORY)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        let owner = authorize_catalog(&self.protocol, &self.restaurant, &self.brand, self.authority.key, &self.staff_member, PERMISSION_MANAGE_MENU)?;
+        MenuItem::validate_recipe(&recipe)?;
         
         self.menu_state.set_inner(MenuItem {
+            version: MenuItem::VERSION,
+            restaurant: owner,
             sku,
             category,
             name,
             price,
-            ingredients,
+            recipe,
             active,
+            dayparts: 0,
         });
 
         Ok(())
@@ -49,6 +61,7 @@ impl<'info> MenuUpdate<'info> {
     pub fn update(
         &mut self,
         active: bool,
+        dayparts: u8,
     ) -> Result<()> {
 
         /*
@@ -59,14 +72,51 @@ impl<'info> MenuUpdate<'info> {
             - Check if the account signing is the primary admin from the multisig wallet.
 
             What the Instruction does:
-            - Updates the active status of the menu item.   
+            - Updates the active status and the dayparts (Schedule.dayparts bitmask, 0 is all day) of the menu item.   
 
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin_state.restaurant.key() == *self.restaurant.key, SetupError::Unauthorized);
+        self.protocol.require_unpaused(PAUSE_INVENTORY)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        let owner = authorize_catalog(&self.protocol, &self.restaurant, &self.brand, self.authority.key, &self.staff_member, PERMISSION_MANAGE_MENU)?;
+        require!(self.menu_state.restaurant == owner, SetupError::Unauthorized);
         
         self.menu_state.active = active;
+        self.menu_state.dayparts = dayparts;
+
+        Ok(())
+    }
+}
+
+impl<'info> MenuRecipeUpdate<'info> {
+    pub fn update(
+        &mut self,
+        recipe: Vec<RecipeLine>,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Menu Recipe Ix:
+
+            Some security check:
+            - The authority must be the owner of the menu item or a staff member with PERMISSION_MANAGE_MENU.
+            - At most MAX_RECIPE_LINES lines, each with a positive quantity and a distinct inventory item.
+
+            What the Instruction does:
+            - Replaces the recipe of the menu item, the account is realloced to fit it. Every order of the
+            item deducts the recipe quantities from the stock of the listed InventoryItem accounts.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_INVENTORY)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        let owner = authorize_catalog(&self.protocol, &self.restaurant, &self.brand, self.authority.key, &self.staff_member, PERMISSION_MANAGE_MENU)?;
+        require!(self.menu_state.restaurant == owner, SetupError::Unauthorized);
+        MenuItem::validate_recipe(&recipe)?;
+        
+        self.menu_state.recipe = recipe;
 
         Ok(())
     }
@@ -91,7 +141,10 @@ impl<'info> MenuRemove<'info> {
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin_state.restaurant.key() == *self.restaurant.key, SetupError::Unauthorized);
+        self.protocol.require_unpaused(PAUSE_INVENTORY)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        let owner = authorize_catalog(&self.protocol, &self.restaurant, &self.brand, self.authority.key, &self.staff_member, PERMISSION_MANAGE_MENU)?;
+        require!(self.menu_state.restaurant == owner, SetupError::Unauthorized);
     
         
         Ok(())
@@ -99,23 +152,23 @@ impl<'info> MenuRemove<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(username: String)]
+#[instruction(sku: u64, category: Pubkey, name: String, price: Money, recipe: Vec<RecipeLine>)]
 pub struct MenuInit<'info> {
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub brand: Option<Account<'info, Brand>>,
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     pub menu_item: SystemAccount<'info>,
     #[account(
         init,
-        payer = restaurant_admin,
-        space = MenuItem::INIT_SPACE + 5,
+        payer = authority,
+        space = MenuItem::INIT_SPACE + name.len() + RecipeLine::SPACE * recipe.len(),
         seeds = [b"menu_state", menu_item.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
@@ -131,18 +184,49 @@ pub struct MenuInit<'info> {
 #[derive(Accounts)]
 pub struct MenuUpdate<'info> {
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub brand: Option<Account<'info, Brand>>,
+    #[account(mut)]
+    pub restaurant: Account<'info, Restaurant>,
+    pub menu_item: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"menu_state", menu_item.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub menu_state: Account<'info, MenuItem>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(recipe: Vec<RecipeLine>)]
+pub struct MenuRecipeUpdate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub brand: Option<Account<'info, Brand>>,
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     pub menu_item: SystemAccount<'info>,
     #[account(
         mut,
+        realloc = MenuItem::INIT_SPACE + menu_state.name.len() + RecipeLine::SPACE * recipe.len(),
+        realloc::payer = authority,
+        realloc::zero = false,
         seeds = [b"menu_state", menu_item.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
@@ -158,19 +242,19 @@ pub struct MenuUpdate<'info> {
 #[derive(Accounts)]
 pub struct MenuRemove<'info> {
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub brand: Option<Account<'info, Brand>>,
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     pub menu_item: SystemAccount<'info>,
     #[account(
         mut,
-        close = restaurant_admin,
+        close = authority,
         seeds = [b"menu_state", menu_item.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
@@ -0,0 +1,144 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+use crate::{
+    state::{
+        Versioned,
+        Protocol,
+        Proposal,
+        CurrencyListing,
+        Admin,
+        Restaurant,
+        Brand,
+        StaffMember,
+        StaffTombstone,
+        Shift,
+        Timesheet,
+        InventoryItem,
+        MenuItem,
+        Customer,
+        CustomerNft,
+        CustomerOrder,
+        Reward,
+        LoyaltyConfig,
+        Schedule,
+        TaxConfig,
+        TaxLedger,
+        LowStockIndex,
+        Vault,
+        TipPool,
+        Table,
+    },
+    errors::ProtocolError,
+};
+
+impl<'info> AccountMigrate<'info> {
+    pub fn migrate(
+        &mut self,
+    ) -> Result<()> {
+
+        /*
+        
+            Migrate Account Ix:
+
+            Some security check:
+            - The account must be owned by the program, Anchor's discriminator tells us which struct it is.
+            - The account must be on an older layout version than the current one.
+
+            What the Instruction does:
+            - Decodes the account with its stored layout version and re-encodes it with the current layout.
+            - Accounts created before versioning have no version byte, their size tells them apart (version 0).
+            - Reallocs the account when the new layout is bigger, the payer covers the extra rent.
+
+        */
+
+        let discriminator: [u8; 8] = self.account.try_borrow_data()?[..8]
+            .try_into()
+            .map_err(|_| ProtocolError::UnsupportedVersion)?;
+
+        match discriminator {
+            Protocol::DISCRIMINATOR => self.upgrade::<Protocol>(),
+            Proposal::DISCRIMINATOR => self.upgrade::<Proposal>(),
+            CurrencyListing::DISCRIMINATOR => self.upgrade::<CurrencyListing>(),
+            Admin::DISCRIMINATOR => self.upgrade::<Admin>(),
+            Restaurant::DISCRIMINATOR => self.upgrade::<Restaurant>(),
+            Brand::DISCRIMINATOR => self.upgrade::<Brand>(),
+            StaffMember::DISCRIMINATOR => self.upgrade::<StaffMember>(),
+            StaffTombstone::DISCRIMINATOR => self.upgrade::<StaffTombstone>(),
+            Shift::DISCRIMINATOR => self.upgrade::<Shift>(),
+            Timesheet::DISCRIMINATOR => self.upgrade::<Timesheet>(),
+            InventoryItem::DISCRIMINATOR => self.upgrade::<InventoryItem>(),
+            MenuItem::DISCRIMINATOR => self.upgrade::<MenuItem>(),
+            Customer::DISCRIMINATOR => self.upgrade::<Customer>(),
+            CustomerNft::DISCRIMINATOR => self.upgrade::<CustomerNft>(),
+            CustomerOrder::DISCRIMINATOR => self.upgrade::<CustomerOrder>(),
+            Reward::DISCRIMINATOR => self.upgrade::<Reward>(),
+            LoyaltyConfig::DISCRIMINATOR => self.upgrade::<LoyaltyConfig>(),
+            Schedule::DISCRIMINATOR => self.upgrade::<Schedule>(),
+            TaxConfig::DISCRIMINATOR => self.upgrade::<TaxConfig>(),
+            TaxLedger::DISCRIMINATOR => self.upgrade::<TaxLedger>(),
+            LowStockIndex::DISCRIMINATOR => self.upgrade::<LowStockIndex>(),
+            Vault::DISCRIMINATOR => self.upgrade::<Vault>(),
+            TipPool::DISCRIMINATOR => self.upgrade::<TipPool>(),
+            Table::DISCRIMINATOR => self.upgrade::<Table>(),
+            _ => err!(ProtocolError::UnsupportedVersion),
+        }
+    }
+
+    fn upgrade<T: Versioned>(&self) -> Result<()> {
+        let account = self.account.to_account_info();
+
+        // the version byte sits right after the discriminator, unversioned accounts are version 0
+        let version = {
+            let data = account.try_borrow_data()?;
+            if T::is_unversioned(&data) {
+                0
+            } else {
+                *data.get(8).ok_or(ProtocolError::UnsupportedVersion)?
+            }
+        };
+        require!(version < T::VERSION, ProtocolError::AccountUpToDate);
+
+        let upgraded = T::upgrade(version, &account.try_borrow_data()?)?;
+        let space = 8 + upgraded.try_to_vec()?.len();
+
+        if space > account.data_len() {
+            let lamports = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+
+            if lamports > 0 {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.payer.to_account_info(),
+                            to: account.clone(),
+                        }
+                    ),
+                    lamports,
+                )?;
+            }
+
+            account.realloc(space, false)?;
+        }
+
+        let mut data = account.try_borrow_mut_data()?;
+        upgraded.try_serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct AccountMigrate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: any account owned by the program, the discriminator tells us how to upgrade it
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
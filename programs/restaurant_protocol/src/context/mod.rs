@@ -1,14 +1,38 @@
 pub mod protocol;
 pub use protocol::*;
 
+pub mod governance;
+pub use governance::*;
+
+pub mod treasury;
+pub use treasury::*;
+
+pub mod migration;
+pub use migration::*;
+
+pub mod currency;
+pub use currency::*;
+
 pub mod admin;
 pub use admin::*;
 
 pub mod restaurant;
 pub use restaurant::*;
 
-pub mod employee;
-pub use employee::*;
+pub mod brand;
+pub use brand::*;
+
+pub mod staff;
+pub use staff::*;
+
+pub mod shift;
+pub use shift::*;
+
+pub mod payroll;
+pub use payroll::*;
+
+pub mod tip;
+pub use tip::*;
 
 pub mod inventory;
 pub use inventory::*;
@@ -23,4 +47,19 @@ pub mod order;
 pub use order::*;
 
 pub mod reward;
-pub use reward::*;
\ No newline at end of file
+pub use reward::*;
+
+pub mod loyalty;
+pub use loyalty::*;
+
+pub mod schedule;
+pub use schedule::*;
+
+pub mod tax;
+pub use tax::*;
+
+pub mod vault;
+pub use vault::*;
+
+pub mod table;
+pub use table::*;
\ No newline at end of file
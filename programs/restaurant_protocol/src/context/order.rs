@@ -1,63 +1,243 @@
 use {
-    anchor_lang::prelude::*,
+    anchor_lang::{prelude::*, system_program},
     anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer},
 };
 use crate::{
+    auth::authorize,
+    events::LowStock,
     state::{
+        Versioned,
         Customer,
         CustomerOrder,
         CustomerNft,
         Protocol,
-        RestaurantAdmin
+        Restaurant,
+        CurrencyListing,
+        LoyaltyConfig,
+        Schedule,
+        TaxConfig,
+        TaxLedger,
+        Vault,
+        TipPool,
+        Table,
+        MenuItem,
+        RecipeLine,
+        InventoryItem,
+        LowStockIndex,
+        Money,
+        Quantity,
+        StaffMember
     },
-    errors::ProtocolError,
+    constant::{
+        PAUSE_ORDERS,
+        PERMISSION_UPDATE_ORDERS,
+        PERMISSION_ISSUE_REFUNDS,
+        MAX_TABLE_ORDERS,
+        TABLE_SEATED,
+        ORDER_PENDING,
+        ORDER_READY,
+        ORDER_COMPLETED,
+        ORDER_FINALIZED,
+        ORDER_CANCELLED,
+    },
+    errors::{SetupError, ProtocolError},
 };
 
+// Adds the recipe of an ordered menu item to the stock used by the order, summed per inventory item in the
+// order they first appear.
+fn add_recipe(usage: &mut Vec<(Pubkey, Quantity)>, recipe: &[RecipeLine]) -> Result<()> {
+    for line in recipe {
+        match usage.iter_mut().find(|(inventory, _)| *inventory == line.inventory) {
+            Some((_, quantity)) => *quantity = quantity.checked_add(line.quantity)?,
+            None => usage.push((line.inventory, line.quantity)),
+        }
+    }
+    Ok(())
+}
+
+// Reads the InventoryItem of a recipe from the remaining accounts, it has to be writable to update its stock.
+fn load_inventory(info: &AccountInfo, inventory: &Pubkey, restaurant: &Pubkey) -> Result<InventoryItem> {
+    require!(
+        info.key == inventory && info.owner == &crate::ID && info.is_writable,
+        ProtocolError::InvalidInventoryItems
+    );
+    let inventory_item = InventoryItem::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(inventory_item.restaurant == *restaurant, ProtocolError::InvalidInventoryItems);
+    Ok(inventory_item)
+}
+
 impl<'info> OrderInit<'info> {
     pub fn add(
         &mut self,
         order_id: u64,
-        total: f32,
         items: Vec<u64>,
+        tip: u64,
+        accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
 
         /*
         
-            Create a new Employee Ix:
+            Create a new Order Ix:
 
             Some security check:
-            
+            - The remaining accounts must be the MenuItem of every ordered sku, in order, followed by the writable
+            InventoryItem of every recipe line in the order they first appear.
+            - Every InventoryItem must belong to the restaurant and hold enough stock for the order.
+            - A dine-in order can only be bound to a seated table of the restaurant.
+            - Outside the Schedule opening hours and menu item dayparts only the owner or a staff member with
+            PERMISSION_UPDATE_ORDERS (authority + staff_member) can place the order.
 
+            What these Instructions do:
+            - Prices the order from its menu items: subtotal, tax from the TaxConfig and total.
+            - Escrows the payment and the optional tip (in currency base units) in the restaurant Vault until the
+            order is completed or cancelled.
+            - Computes the reward points of the subtotal using the LoyaltyConfig, they are credited and the tax is
+            recorded in the TaxLedger once the payment is released.
+            - Deducts the recipe quantities of every ordered item from the stock and sets the last order time.
+            - Lists the inventory items that drop below their reorder point in the LowStockIndex and emits LowStock.
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        require!(self.currency.key() == self.restaurant.currency, ProtocolError::CurrencyMismatch);
+        require!(self.currency_listing.listed, ProtocolError::CurrencyNotListed);
+
+        let now = Clock::get()?.unix_timestamp;
+        let staffed = match &self.authority {
+            Some(authority) => {
+                authorize(&self.protocol, &self.restaurant, authority.key, &self.staff_member, PERMISSION_UPDATE_ORDERS)?;
+                true
+            },
+            None => false,
+        };
+        require!(staffed || self.schedule.is_open(now), ProtocolError::RestaurantClosed);
+        require!(accounts.len() >= items.len(), ProtocolError::InvalidMenuItems);
+        let (menu_items, inventory_items) = accounts.split_at(items.len());
+
+        let mut lines = Vec::with_capacity(items.len());
+        let mut usage: Vec<(Pubkey, Quantity)> = Vec::new();
+        for (sku, info) in items.iter().zip(menu_items) {
+            require!(info.owner == &crate::ID, ProtocolError::InvalidMenuItems);
+            let menu_item = MenuItem::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(
+                self.restaurant.offers(&self.restaurant.key(), &menu_item.restaurant) && menu_item.sku == *sku && menu_item.active,
+                ProtocolError::InvalidMenuItems
+            );
+            require!(staffed || self.schedule.serves(menu_item.dayparts, now), ProtocolError::MenuItemUnavailable);
+            lines.push((menu_item.category, menu_item.price.rescale(self.currency.decimals)?));
+            add_recipe(&mut usage, &menu_item.recipe)?;
+        }
+
+        require!(inventory_items.len() == usage.len(), ProtocolError::InvalidInventoryItems);
+        for ((inventory, quantity), info) in usage.iter().zip(inventory_items) {
+            let mut inventory_item = load_inventory(info, inventory, &self.restaurant.key())?;
+            require!(inventory_item.stock.unit == quantity.unit, ProtocolError::UnitMismatch);
+            require!(inventory_item.stock.amount >= quantity.amount, ProtocolError::InsufficientStock);
+
+            let was_low = inventory_item.is_low();
+            inventory_item.stock = inventory_item.stock.checked_sub(*quantity)?;
+            inventory_item.last_order = now as u64;
+            inventory_item.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+            self.low_stock_index.refresh(*inventory, &inventory_item)?;
+            if !was_low && inventory_item.is_low() {
+                emit!(LowStock {
+                    restaurant: self.restaurant.key(),
+                    inventory: *inventory,
+                    sku: inventory_item.sku,
+                    stock: inventory_item.stock,
+                    reorder_point: inventory_item.reorder_point,
+                    par: inventory_item.par,
+                });
+            }
+        }
+
+        let mut subtotal = Money::zero(self.currency.decimals);
+        let mut tax = Money::zero(self.currency.decimals);
+        for (category, price) in &lines {
+            subtotal = subtotal.checked_add(*price)?;
+            tax = tax.checked_add(self.tax_config.tax(category, *price)?)?;
+        }
+        let total = subtotal.checked_add(tax)?;
+        let amount = total.amount;
+        let escrow = amount.checked_add(tip).ok_or(ProtocolError::MoneyOverflow)?;
 
         transfer(
             CpiContext::new(
                 self.token_program.to_account_info(),
                 Transfer {
                     from: self.customer_currency_ata.to_account_info(),
-                    to: self.restaurant_owner_currency_ata.to_account_info(),
+                    to: self.vault_tokens.to_account_info(),
                     authority: self.customer.to_account_info(),
                 }
             ),
-            (total * (10u64.pow(self.currency.decimals as u32) as f32)) as u64,
+            escrow,
         )?;
 
-        let new_reward_amount = ((total * 10.0) as u64) + self.customer_nft.reward_points;
+        // first order in this currency creates the vault and its tip pool
+        if self.vault.version == 0 {
+            self.vault.version = Vault::VERSION;
+            self.vault.restaurant = self.restaurant.key();
+            self.vault.currency = self.currency.key();
+        }
+        if self.tip_pool.version == 0 {
+            self.tip_pool.version = TipPool::VERSION;
+            self.tip_pool.restaurant = self.restaurant.key();
+            self.tip_pool.vault = self.vault.key();
+            self.tip_pool.period_start = now;
+        }
+        self.vault.escrowed = self.vault.escrowed.checked_add(escrow).ok_or(ProtocolError::MoneyOverflow)?;
+
+        let points = self.loyalty_config.points(subtotal, &lines, now)?;
 
-        self.customer_nft.reward_points = new_reward_amount as u64;
+        // the stock lines are only known once the recipes are read, the customer pays the extra rent
+        let space = CustomerOrder::INIT_SPACE + 8 * items.len() + RecipeLine::SPACE * usage.len();
+        let order_info = self.order_state.to_account_info();
+        if space > order_info.data_len() {
+            let lamports = Rent::get()?.minimum_balance(space).saturating_sub(order_info.lamports());
+            if lamports > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.customer.to_account_info(),
+                            to: order_info.clone(),
+                        }
+                    ),
+                    lamports,
+                )?;
+            }
+            order_info.realloc(space, false)?;
+        }
         
         self.order_state.set_inner(CustomerOrder {
+            version: CustomerOrder::VERSION,
             order_id,
+            restaurant: self.restaurant.key(),
             customer: self.customer.key(),
             items,
-            total, // highest f16 number is 65504.0
-            status: 0,
-            created_at: Clock::get()?.unix_timestamp,
+            total,
+            status: ORDER_PENDING,
+            created_at: now,
             updated_at: 0,
+            table: self.table.as_ref().map(|table| table.key()),
+            subtotal,
+            tax,
+            escrow,
+            tip,
+            points,
+            stock: usage.iter().map(|(inventory, quantity)| RecipeLine { inventory: *inventory, quantity: *quantity }).collect(),
         });
 
+        if let Some(table) = &mut self.table {
+            require!(table.status == TABLE_SEATED, ProtocolError::InvalidTableStatus);
+            require!(table.open_orders.len() < MAX_TABLE_ORDERS, ProtocolError::InvalidTable);
+            table.open_orders.push(self.order_state.key());
+        }
+
+        self.restaurant.pending_orders = self.restaurant.pending_orders.checked_add(1).ok_or(ProtocolError::CountOverflow)?;
+
         Ok(())
     }
 }
@@ -66,18 +246,95 @@ impl<'info> OrderUpdate<'info> {
     pub fn update(
         &mut self,
         status: u8,
+        bumps: OrderUpdateBumps,
     ) -> Result<()> {
 
         /*
         
 
             Some security check:
-            
+            - The authority must be the restaurant owner or a staff member with PERMISSION_UPDATE_ORDERS whose
+            role allows the transition (kitchen: pending <-> ready, expo: ready -> completed, cashier: ready ->
+            completed -> finalized).
+            - Orders are cancelled through cancel_order so the customer gets refunded.
+
+            What these Instructions do:
+            - Once the order is completed or finalized, releases its escrowed payment: the tip goes to the TipPool, the
+            protocol fee goes to the treasury of the currency and the rest is settled in the Vault for the
+            owner to withdraw. The tax of the order is recorded in the TaxLedger and its reward points are credited
+            to the customer, refunded orders are never reported.
 
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin_state.restaurant == self.restaurant.key(), ProtocolError::UnauthorizedAdmin);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_UPDATE_ORDERS)?;
+        require!(
+            matches!(status, ORDER_PENDING | ORDER_READY | ORDER_COMPLETED | ORDER_FINALIZED),
+            ProtocolError::InvalidOrderStatus
+        );
+        if let Some(staff_member) = &self.staff_member {
+            if *self.authority.key != self.restaurant.owner {
+                require!(staff_member.can_move_order(self.order_state.status, status), SetupError::Unauthorized);
+            }
+        }
+
+        let escrow = self.order_state.escrow;
+        if !CustomerOrder::is_open_status(status) && escrow > 0 {
+            let tip = self.order_state.tip;
+            let payment = escrow.checked_sub(tip).ok_or(ProtocolError::MoneyUnderflow)?;
+            let fee = (payment as u128 * self.restaurant.fee_bps(&self.protocol) as u128 / 10_000) as u64;
+
+            // protocol fee goes to the treasury of the currency
+            if fee > 0 {
+                let restaurant = self.restaurant.key();
+                let seeds: &[&[u8]; 4] = &[
+                    b"vault",
+                    restaurant.as_ref(),
+                    self.restaurant.currency.as_ref(),
+                    &[bumps.vault],
+                ];
+                let signer_seeds = &[&seeds[..]];
+
+                transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        Transfer {
+                            from: self.vault_tokens.to_account_info(),
+                            to: self.treasury.to_account_info(),
+                            authority: self.vault.to_account_info(),
+                        },
+                        signer_seeds
+                    ),
+                    fee,
+                )?;
+            }
+
+            self.vault.escrowed = self.vault.escrowed.checked_sub(escrow).ok_or(ProtocolError::MoneyUnderflow)?;
+            self.vault.settled = payment
+                .checked_sub(fee)
+                .and_then(|settled| self.vault.settled.checked_add(settled))
+                .ok_or(ProtocolError::MoneyOverflow)?;
+            self.tip_pool.balance = self.tip_pool.balance.checked_add(tip).ok_or(ProtocolError::MoneyOverflow)?;
+            self.order_state.escrow = 0;
+
+            self.tax_ledger.collected = self.tax_ledger.collected
+                .checked_add(self.order_state.tax.amount)
+                .ok_or(ProtocolError::MoneyOverflow)?;
+            self.tax_ledger.order_count = self.tax_ledger.order_count.checked_add(1).ok_or(ProtocolError::CountOverflow)?;
+
+            self.customer_nft.reward_points = self.customer_nft.reward_points
+                .checked_add(self.order_state.points)
+                .ok_or(ProtocolError::InvalidLoyaltyConfig)?;
+        }
+
+        // keep the open order count in sync, orders can move back to pending
+        if self.order_state.is_open() && !CustomerOrder::is_open_status(status) {
+            self.restaurant.pending_orders = self.restaurant.pending_orders.checked_sub(1).ok_or(ProtocolError::CountOverflow)?;
+        } else if !self.order_state.is_open() && CustomerOrder::is_open_status(status) {
+            self.restaurant.pending_orders = self.restaurant.pending_orders.checked_add(1).ok_or(ProtocolError::CountOverflow)?;
+        }
         
         self.order_state.status = status;
         self.order_state.updated_at = Clock::get()?.unix_timestamp;
@@ -89,20 +346,90 @@ impl<'info> OrderUpdate<'info> {
 impl<'info> OrderCancel<'info> {
     pub fn cancel(
         &mut self,
+        accounts: &[AccountInfo<'info>],
+        bumps: OrderCancelBumps,
     ) -> Result<()> {
 
         /*
         
 
             Some security check:
-            
+            - The signer must be the customer of the order, or the restaurant owner / a staff member
+            with the ISSUE_REFUNDS permission cancelling on the customer behalf, kitchen and expo can't.
+            - Customers can only cancel pending orders, later statuses are left to the owner and staff.
+            - Only open orders can be cancelled, completed and finalized orders already released their payment.
+            - The remaining accounts must be the writable InventoryItem of every line of CustomerOrder.stock, in order.
+
+            What these Instructions do:
+            - Refunds the escrowed payment from the Vault to the customer.
+            - Puts the stock deducted when the order was placed back and refreshes the LowStockIndex.
 
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.customer.key() == self.order_state.customer, ProtocolError::UnauthorizedAdmin);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+
+        require!(self.order_state.is_open(), ProtocolError::InvalidOrderStatus);
+
+        if self.authority.key() == self.order_state.customer {
+            require!(self.order_state.status == ORDER_PENDING, ProtocolError::InvalidOrderStatus);
+        } else {
+            authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_ISSUE_REFUNDS)?;
+            if let Some(staff_member) = &self.staff_member {
+                if *self.authority.key != self.restaurant.owner {
+                    require!(staff_member.handles_payments(), SetupError::Unauthorized);
+                }
+            }
+        }
+
+        // the stock deducted when the order was placed, recipe edits since then don't matter
+        let stock = &self.order_state.stock;
+        require!(accounts.len() == stock.len(), ProtocolError::InvalidInventoryItems);
+        for (line, info) in stock.iter().zip(accounts) {
+            let mut inventory_item = load_inventory(info, &line.inventory, &self.restaurant.key())?;
+            inventory_item.stock = inventory_item.stock.checked_add(line.quantity)?;
+            inventory_item.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+            self.low_stock_index.refresh(line.inventory, &inventory_item)?;
+        }
+
+        self.restaurant.pending_orders = self.restaurant.pending_orders.checked_sub(1).ok_or(ProtocolError::CountOverflow)?;
+
+        if let Some(table) = self.order_state.table {
+            let table_state = self.table.as_mut().ok_or(ProtocolError::InvalidTable)?;
+            require!(table_state.key() == table, ProtocolError::InvalidTable);
+            table_state.remove_order(&self.order_state.key());
+        }
+
+        let escrow = self.order_state.escrow;
+        if escrow > 0 {
+            let restaurant = self.restaurant.key();
+            let seeds: &[&[u8]; 4] = &[
+                b"vault",
+                restaurant.as_ref(),
+                self.restaurant.currency.as_ref(),
+                &[bumps.vault],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.vault_tokens.to_account_info(),
+                        to: self.customer_currency_ata.to_account_info(),
+                        authority: self.vault.to_account_info(),
+                    },
+                    signer_seeds
+                ),
+                escrow,
+            )?;
+
+            self.vault.escrowed = self.vault.escrowed.checked_sub(escrow).ok_or(ProtocolError::MoneyUnderflow)?;
+            self.order_state.escrow = 0;
+        }
         
-        self.order_state.status = 4;
+        self.order_state.status = ORDER_CANCELLED;
         self.order_state.updated_at = Clock::get()?.unix_timestamp;
 
         Ok(())
@@ -118,12 +445,30 @@ impl<'info> OrderClose<'info> {
         
 
             Some security check:
-            
+            - The authority must be the restaurant owner or a staff member with PERMISSION_UPDATE_ORDERS,
+            kitchen and expo can't.
+            - The order payment can't be escrowed anymore.
 
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin_state.restaurant == self.restaurant.key(), ProtocolError::UnauthorizedAdmin);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_UPDATE_ORDERS)?;
+        if let Some(staff_member) = &self.staff_member {
+            if *self.authority.key != self.restaurant.owner {
+                require!(staff_member.handles_payments(), SetupError::Unauthorized);
+            }
+        }
+        require!(self.order_state.escrow == 0, ProtocolError::OrderEscrowed);
+
+        self.restaurant.pending_orders = self.restaurant.pending_orders.checked_sub(1).ok_or(ProtocolError::CountOverflow)?;
+
+        if let Some(table) = self.order_state.table {
+            let table_state = self.table.as_mut().ok_or(ProtocolError::InvalidTable)?;
+            require!(table_state.key() == table, ProtocolError::InvalidTable);
+            table_state.remove_order(&self.order_state.key());
+        }
 
         Ok(())
     }
@@ -135,23 +480,18 @@ impl<'info> OrderClose<'info> {
     items: Vec<u64>,
 )]
 pub struct OrderInit<'info> {
-    #[account(mut)]
-    /// CHECK
-    pub restaurant_owner: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [b"restaurant", restaurant_owner.key().as_ref()],
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
         bump,
     )]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     pub currency: Account<'info, Mint>,
     #[account(
-        mut,
-        associated_token::mint = currency,
-        associated_token::authority = restaurant_owner,
+        seeds = [b"currency", currency.key().as_ref()],
+        bump
     )]
-    pub restaurant_owner_currency_ata: Account<'info, TokenAccount>,
+    pub currency_listing: Account<'info, CurrencyListing>,
     #[account(mut)]
     pub customer: Signer<'info>,
     #[account(
@@ -161,22 +501,77 @@ pub struct OrderInit<'info> {
     )]
     pub customer_currency_ata: Account<'info, TokenAccount>,
     #[account(
-        seeds = [b"customer", customer.key().as_ref(), restaurant.key().as_ref()],
+        init_if_needed,
+        payer = customer,
+        space = Vault::INIT_SPACE,
+        seeds = [b"vault", restaurant.key().as_ref(), currency.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = customer,
+        token::mint = currency,
+        token::authority = vault,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = customer,
+        space = TipPool::INIT_SPACE,
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+    #[account(
+        seeds = [b"customer", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump,
     )]
     pub customer_profile: Account<'info, Customer>,
     #[account(
-        mut,
-        seeds = [b"member_nft", customer.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"member_nft", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump,
     )] 
     pub customer_nft: Account<'info, CustomerNft>,
+    #[account(
+        seeds = [b"loyalty", restaurant.key().as_ref()],
+        bump,
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+    #[account(
+        seeds = [b"schedule", restaurant.key().as_ref()],
+        bump,
+    )]
+    pub schedule: Account<'info, Schedule>,
+    #[account(
+        seeds = [b"tax", restaurant.key().as_ref()],
+        bump,
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+    #[account(
+        mut,
+        seeds = [b"low_stock", restaurant.key().as_ref()],
+        bump
+    )]
+    pub low_stock_index: Account<'info, LowStockIndex>,
+    /// owner or staff member placing the order on the customer behalf, bypasses the schedule
+    pub authority: Option<Signer<'info>>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    /// table the dine-in order is bound to
+    #[account(
+        mut,
+        seeds = [b"table", restaurant.key().as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Option<Account<'info, Table>>,
     /// CHECK
     pub order: AccountInfo<'info>,
     #[account(
         init,
         payer = customer,
-        space = CustomerOrder::INIT_SPACE + 5,
+        space = CustomerOrder::INIT_SPACE + 8 * items.len(),
         seeds = [b"order_state", order.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
@@ -193,15 +588,14 @@ pub struct OrderInit<'info> {
 #[derive(Accounts)]
 pub struct OrderUpdate<'info> {
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"restaurant", restaurant_admin.key.as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump,
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
     /// CHECK
     pub order: AccountInfo<'info>,
     #[account(
@@ -210,26 +604,103 @@ pub struct OrderUpdate<'info> {
         bump
     )]
     pub order_state: Account<'info, CustomerOrder>,
+    #[account(
+        mut,
+        seeds = [b"member_nft", order_state.customer.as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
+        bump,
+    )]
+    pub customer_nft: Account<'info, CustomerNft>,
+    #[account(address = restaurant.currency)]
+    pub currency: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"vault", restaurant.key().as_ref(), currency.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+    #[account(
+        mut,
+        seeds = [b"tax_ledger", restaurant.key().as_ref()],
+        bump,
+    )]
+    pub tax_ledger: Account<'info, TaxLedger>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = currency,
+        token::authority = protocol,
+        seeds = [b"treasury", currency.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
     #[account(
         seeds = [b"protocol"],
         bump,
     )]
     pub protocol: Account<'info, Protocol>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct OrderCancel<'info> {
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
-    #[account(mut)]
-    pub customer: Signer<'info>,
+    pub restaurant: Account<'info, Restaurant>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    /// CHECK: the customer of the order, receives the rent of the order account
+    #[account(
+        mut,
+        address = order_state.customer,
+    )]
+    pub customer: AccountInfo<'info>,
     #[account(
-        seeds = [b"customer", customer.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"customer", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump,
     )]
     pub customer_profile: Account<'info, Customer>,
+    #[account(address = restaurant.currency)]
+    pub currency: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = currency,
+        associated_token::authority = customer,
+    )]
+    pub customer_currency_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"vault", restaurant.key().as_ref(), currency.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"low_stock", restaurant.key().as_ref()],
+        bump
+    )]
+    pub low_stock_index: Account<'info, LowStockIndex>,
     /// CHECK
     pub order: AccountInfo<'info>,
     #[account(
@@ -239,35 +710,47 @@ pub struct OrderCancel<'info> {
         bump
     )]
     pub order_state: Account<'info, CustomerOrder>,
+    #[account(
+        mut,
+        seeds = [b"table", restaurant.key().as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Option<Account<'info, Table>>,
     #[account(
         seeds = [b"protocol"],
         bump,
     )]
     pub protocol: Account<'info, Protocol>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct OrderClose<'info> {
     #[account(mut)]
-    /// CHECK
-    pub restaurant: AccountInfo<'info>,
+    pub restaurant: Account<'info, Restaurant>,
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"restaurant", restaurant_admin.key.as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump,
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
     /// CHECK
     pub order: AccountInfo<'info>,
     #[account(
         mut,
-        close = restaurant_admin,
+        close = authority,
         seeds = [b"order_state", order.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
     pub order_state: Account<'info, CustomerOrder>,
+    #[account(
+        mut,
+        seeds = [b"table", restaurant.key().as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Option<Account<'info, Table>>,
     #[account(
         seeds = [b"protocol"],
         bump,
@@ -0,0 +1,207 @@
+use {
+    anchor_lang::{prelude::*, Discriminator},
+    anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer},
+};
+use crate::{
+    auth::authorize,
+    events::WagesPaid,
+    state::{
+        Restaurant,
+        StaffMember,
+        StaffTombstone,
+        Shift,
+        Vault,
+        Protocol
+    },
+    constant::{PAUSE_STAFF, PERMISSION_RUN_PAYROLL},
+    errors::ProtocolError,
+};
+
+// Staff member being paid, read from its StaffMember record or from the StaffTombstone left by a termination
+pub struct Payee {
+    pub publickey: Pubkey,
+    pub hourly_wage: u64,
+    pub worked_from: i64,         // Only shifts clocked in from here ...
+    pub worked_until: i64,        // ... up to here belong to this record
+}
+
+pub fn load_payee(info: &AccountInfo, restaurant: &Pubkey) -> Result<Payee> {
+    require!(info.owner == &crate::ID, ProtocolError::InvalidPayroll);
+    let data = info.try_borrow_data()?;
+    let discriminator: [u8; 8] = data
+        .get(..8)
+        .and_then(|discriminator| discriminator.try_into().ok())
+        .ok_or(ProtocolError::InvalidPayroll)?;
+
+    // a rehired member has both, the tombstone pays everything worked before the last termination
+    let payee = match discriminator {
+        StaffMember::DISCRIMINATOR => {
+            let member = StaffMember::try_deserialize(&mut &data[..])?;
+            require!(member.restaurant == *restaurant, ProtocolError::InvalidPayroll);
+            Payee {
+                publickey: member.publickey,
+                hourly_wage: member.hourly_wage,
+                worked_from: member.initialized,
+                worked_until: i64::MAX,
+            }
+        },
+        StaffTombstone::DISCRIMINATOR => {
+            let tombstone = StaffTombstone::try_deserialize(&mut &data[..])?;
+            require!(tombstone.restaurant == *restaurant, ProtocolError::InvalidPayroll);
+            Payee {
+                publickey: tombstone.member,
+                hourly_wage: tombstone.hourly_wage,
+                worked_from: 0,
+                worked_until: tombstone.terminated_at,
+            }
+        },
+        _ => return err!(ProtocolError::InvalidPayroll),
+    };
+
+    Ok(payee)
+}
+
+impl<'info> PayrollRun<'info> {
+    pub fn run(
+        &mut self,
+        shift_counts: Vec<u8>,
+        accounts: &[AccountInfo<'info>],
+        bumps: PayrollRunBumps,
+    ) -> Result<()> {
+
+        /*
+        
+            Run Payroll Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the RUN_PAYROLL permission.
+            - For every entry of shift_counts the remaining accounts hold the StaffMember record, its currency
+            token account and that many of its clocked out, unpaid shifts at this restaurant.
+            - A terminated member is paid through its StaffTombstone instead, only for the shifts clocked in
+            before the termination.
+            - Wages are paid out of the settled funds of the Vault, escrowed order payments can't be used.
+
+            What these Instructions do:
+            - Pays every staff member its hourly wage for the seconds worked in the shifts.
+            - Marks the shifts as paid so they can't be paid twice.
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_RUN_PAYROLL)?;
+        require!(
+            accounts.len() == shift_counts.iter().map(|count| 2 + *count as usize).sum::<usize>(),
+            ProtocolError::InvalidPayroll
+        );
+
+        let restaurant = self.restaurant.key();
+        let seeds: &[&[u8]; 4] = &[
+            b"vault",
+            restaurant.as_ref(),
+            self.restaurant.currency.as_ref(),
+            &[bumps.vault],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let mut accounts = accounts.iter();
+        for count in shift_counts {
+            let (member_info, destination) = (
+                accounts.next().ok_or(ProtocolError::InvalidPayroll)?,
+                accounts.next().ok_or(ProtocolError::InvalidPayroll)?,
+            );
+
+            let member = load_payee(member_info, &restaurant)?;
+
+            require!(destination.owner == &self.token_program.key(), ProtocolError::InvalidPayroll);
+            let token_account = TokenAccount::try_deserialize(&mut &destination.try_borrow_data()?[..])?;
+            require!(
+                token_account.owner == member.publickey && token_account.mint == self.currency.key(),
+                ProtocolError::InvalidPayroll
+            );
+
+            let mut seconds = 0u64;
+            for _ in 0..count {
+                let shift_info = accounts.next().ok_or(ProtocolError::InvalidPayroll)?;
+                require!(shift_info.owner == &crate::ID && shift_info.is_writable, ProtocolError::InvalidPayroll);
+
+                // written back right away so a shift passed twice is caught as paid
+                let mut data = shift_info.try_borrow_mut_data()?;
+                let mut shift = Shift::try_deserialize(&mut &data[..])?;
+                require!(
+                    shift.restaurant == restaurant && shift.member == member.publickey,
+                    ProtocolError::InvalidPayroll
+                );
+                require!(shift.clock_out != 0 && !shift.paid, ProtocolError::InvalidShift);
+                require!(
+                    shift.clock_in >= member.worked_from && shift.clock_in < member.worked_until,
+                    ProtocolError::InvalidShift
+                );
+
+                seconds = seconds.checked_add(shift.seconds()).ok_or(ProtocolError::CountOverflow)?;
+                shift.paid = true;
+                shift.try_serialize(&mut &mut data[..])?;
+            }
+
+            let amount = u64::try_from(seconds as u128 * member.hourly_wage as u128 / 3_600).map_err(|_| ProtocolError::MoneyOverflow)?;
+            require!(amount <= self.vault.settled, ProtocolError::InsufficientFunds);
+
+            if amount > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        Transfer {
+                            from: self.vault_tokens.to_account_info(),
+                            to: destination.clone(),
+                            authority: self.vault.to_account_info(),
+                        },
+                        signer_seeds
+                    ),
+                    amount,
+                )?;
+                self.vault.settled = self.vault.settled.checked_sub(amount).ok_or(ProtocolError::MoneyUnderflow)?;
+            }
+
+            emit!(WagesPaid {
+                restaurant,
+                member: member.publickey,
+                seconds,
+                shifts: count as u32,
+                amount,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct PayrollRun<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(address = restaurant.currency)]
+    pub currency: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"vault", restaurant.key().as_ref(), currency.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub token_program: Program<'info, Token>,
+}
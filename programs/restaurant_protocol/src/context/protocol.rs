@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::{
-    state::Protocol,
-    constant::multisig_wallet,
+    state::{Protocol, Versioned},
+    constant::{multisig_wallet, DEFAULT_ADMIN_COOLDOWN},
     errors::SetupError,
 };
 
@@ -10,7 +10,7 @@ pub struct ProtocolSetting<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
     #[account(
-        init_if_needed,
+        init,
         payer = admin,
         space = Protocol::INIT_SPACE,
         seeds = [b"protocol"],
@@ -24,37 +24,35 @@ impl<'info> ProtocolSetting<'info> {
 
     /*
         
-        Change Protocol Settings Ix:
+        Initialize Protocol Ix:
 
         Some security check:
         - Check if the account that is interacting with this instruction is the mutlisig account 
-        of the team that is the highest security clearance for the enitre protocol.
+        of the team, this is only used to bootstrap the protocol. After that every sensitive
+        action goes through the governance proposals of the authority set.
 
         What these Instructions do:
-        - Initialize the Protocol account with the new settings.
-        - Toggle the lock on the Protocol: render the protocol useless/useful.
+        - Initialize the Protocol account in a locked state.
+        - Set the governance authority set and the approval threshold (M-of-N).
+        - Set the default activation cooldown (12h) for new admins and employees.
     */
 
     pub fn initialize_protocol(
         &mut self,
+        authorities: Vec<Pubkey>,
+        threshold: u8,
     ) -> Result<()> {
 
         require!(self.admin.key() == multisig_wallet::id(), SetupError::Unauthorized);
         
+        self.protocol.version = Protocol::VERSION;
         self.protocol.locked = true;
-
-        Ok(())
-    }
-
-    pub fn change_locked_setting(
-        &mut self,
-    ) -> Result<()> {
-
-        require!(self.admin.key() == multisig_wallet::id(), SetupError::Unauthorized);
-        
-        self.protocol.locked = !self.protocol.locked;
+        self.protocol.proposal_count = 0;
+        self.protocol.admin_cooldown = DEFAULT_ADMIN_COOLDOWN;
+        self.protocol.fee_bps = 0;
+        self.protocol.paused = 0;
+        self.protocol.set_authorities(authorities, threshold)?;
 
         Ok(())
     }
 }
-
@@ -3,14 +3,50 @@ use anchor_lang::{
         sysvar::rent::ID as RENT_ID,
         program::{invoke, invoke_signed}
     },
-    prelude::*
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
 };
+use crate::auth::authorize;
+use crate::events::{RestaurantUpdated, RestaurantOwnerTransferred};
 pub use anchor_spl::token_2022::Token2022;
-use crate::state::{Restaurant, Protocol, Admin};
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::state::{
+    Restaurant,
+    Brand,
+    Protocol,
+    Admin,
+    CurrencyListing,
+    LoyaltyConfig,
+    Schedule,
+    TaxConfig,
+    TaxLedger,
+    LowStockIndex,
+    Vault,
+    TipPool,
+    StaffMember,
+    Shift,
+    Timesheet,
+    InventoryItem,
+    MenuItem,
+    CustomerOrder,
+    Reward,
+    Table,
+    Versioned,
+};
 use crate::errors::ProtocolError;
+use crate::constant::{MAX_MEMBERSHIPS, PERMISSION_MANAGE_RESTAURANT};
 pub use spl_token_2022::{
     extension::ExtensionType,
     extension::group_pointer::instruction::initialize as initialize_group_pointer,
+    extension::metadata_pointer::instruction::initialize as initialize_metadata_pointer,
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+    instruction::{initialize_mint2, initialize_mint_close_authority, close_account},
+};
+pub use spl_token_group_interface::instruction::initialize_group;
+pub use spl_token_metadata_interface::{
+    state::{TokenMetadata, Field},
+    instruction::{initialize as initialize_metadata_account, update_field as update_metadata_account},
 };
 
 impl<'info> RestaurantInit<'info> {
@@ -32,18 +68,26 @@ impl<'info> RestaurantInit<'info> {
             - The admin_state.publickey must match the signing admin.
 
             What these Instructions do:
-            - Creates a Collection that can be used to mint NFTs.
+            - Creates a Collection mint that is a Token-2022 group, customer memberships are its members.
+            - The auth PDA is the mint, group update and close authority of the collection.
+            - Creates the LoyaltyConfig with the default earning rules and an always open Schedule.
+            - Creates an untaxed TaxConfig and the TaxLedger the collected tax is reported from.
+            - Creates the empty LowStockIndex listing the inventory items below par.
+            - Stores the restaurant name, symbol and url as the Token-2022 metadata of the collection mint.
         */
 
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
         require!(self.admin_state.publickey == *self.admin.key, ProtocolError::UnauthorizedAdmin);
+        self.protocol.require_active(self.admin_state.initialized)?;
 
         // sanity check
+        require!(self.currency_listing.listed, ProtocolError::CurrencyNotListed);
 
        
 
         self.restaurant.set_inner(
             Restaurant {
+                version: Restaurant::VERSION,
                 reference,
                 name,
                 symbol,
@@ -51,18 +95,53 @@ impl<'info> RestaurantInit<'info> {
                 currency,
                 url,
                 customer_count: 0,
+                protocol_fee_bps: None,
+                paused: false,
+                pending_orders: 0,
+                seed: *self.owner.key,
+                pending_owner: None,
+                brand: None,
             }
         );
 
+        let restaurant = self.restaurant.key();
+        self.loyalty_config.set_inner(LoyaltyConfig::new(restaurant));
+        self.schedule.set_inner(Schedule::new(restaurant));
+        self.tax_config.set_inner(TaxConfig::new(restaurant));
+        self.tax_ledger.set_inner(TaxLedger::new(restaurant, Clock::get()?.unix_timestamp));
+        self.low_stock_index.set_inner(LowStockIndex::new(restaurant));
+
         // Step 1: Initialize Account
         let size = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
             &[
-                ExtensionType::GroupPointer
+                ExtensionType::GroupPointer,
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::MetadataPointer,
+            ],
+        ).unwrap();
+
+        let metadata = TokenMetadata {
+            update_authority: spl_pod::optional_keys::OptionalNonZeroPubkey::try_from(Some(self.auth.key())).unwrap(),
+            mint: self.mint.key(),
+            name: self.restaurant.name.to_string(),
+            symbol: self.restaurant.symbol.to_string(),
+            uri: self.restaurant.url.to_string(),
+            additional_metadata: vec![],
+        };
+
+        // the group and metadata are allocated by the token program, the mint only has to be funded for them
+        let group_size = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &[
+                ExtensionType::GroupPointer,
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::MetadataPointer,
+                ExtensionType::TokenGroup,
             ],
         ).unwrap();
 
+        let extension_extra_space = metadata.tlv_size_of().unwrap();
         let rent = &Rent::from_account_info(&self.rent.to_account_info())?;
-        let lamports = rent.minimum_balance(size );
+        let lamports = rent.minimum_balance(group_size + extension_extra_space);
 
         let collection_key = self.restaurant.key();
         let seeds: &[&[u8]; 3] = &[
@@ -80,7 +159,7 @@ impl<'info> RestaurantInit<'info> {
                 (size).try_into().unwrap(),
                 &spl_token_2022::id(),
             ),
-            &vec![
+            &[
                 self.admin.to_account_info(),
                 self.mint.to_account_info(),
             ],
@@ -91,23 +170,527 @@ impl<'info> RestaurantInit<'info> {
             &initialize_group_pointer(
                 &self.token_2022_program.key(),
                 &self.mint.key(),
-                Some(self.admin.key()),
+                Some(self.auth.key()),
                 Some(self.mint.key()),
             )?,
-            &vec![
+            &[
                 self.mint.to_account_info(),
             ],  
         )?;
 
+        invoke(
+            &initialize_metadata_pointer(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                Some(self.auth.key()),
+                Some(self.mint.key()),
+            )?,
+            &[
+                self.mint.to_account_info(),
+            ],
+        )?;
+
+        invoke(
+            &initialize_mint_close_authority(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                Some(&self.auth.key()),
+            )?,
+            &[
+                self.mint.to_account_info(),
+            ],
+        )?;
+
+        invoke(
+            &initialize_mint2(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                None,
+                0,
+            )?,
+            &[
+                self.mint.to_account_info(),
+            ],
+        )?;
+
+        let seeds: &[&[u8]; 2] = &[
+            b"auth",
+            &[bumps.auth],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        invoke_signed(
+            &initialize_metadata_account(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                metadata.name,
+                metadata.symbol,
+                metadata.uri,
+            ),
+            &[
+                self.mint.to_account_info(),
+                self.auth.to_account_info(),
+            ],
+            signer_seeds
+        )?;
+
+        invoke_signed(
+            &initialize_group(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.mint.key(),
+                &self.auth.key(),
+                Some(self.auth.key()),
+                MAX_MEMBERSHIPS,
+            ),
+            &[
+                self.mint.to_account_info(),
+                self.auth.to_account_info(),
+            ],
+            signer_seeds
+        )?;
+
+        Ok(())
+    }
+}
+
+impl<'info> RestaurantAccountsInit<'info> {
+    pub fn init_accounts(&mut self) -> Result<()> {
+
+        /*
+        
+            Init Restaurant Accounts Ix:
+
+            Some security check:
+            - Anyone can pay for it, the accounts only get the defaults add_restaurant creates them with.
+            - Accounts that already exist are left untouched, old versions have to be migrated first.
+
+            What these Instructions do:
+            - Creates the LoyaltyConfig, Schedule, TaxConfig, TaxLedger and LowStockIndex add_order needs
+            for a restaurant created before they existed.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+
+        let restaurant = self.restaurant.key();
+        if self.loyalty_config.version == 0 {
+            self.loyalty_config.set_inner(LoyaltyConfig::new(restaurant));
+        }
+        if self.schedule.version == 0 {
+            self.schedule.set_inner(Schedule::new(restaurant));
+        }
+        if self.tax_config.version == 0 {
+            self.tax_config.set_inner(TaxConfig::new(restaurant));
+        }
+        if self.tax_ledger.version == 0 {
+            self.tax_ledger.set_inner(TaxLedger::new(restaurant, Clock::get()?.unix_timestamp));
+        }
+        if self.low_stock_index.version == 0 {
+            self.low_stock_index.set_inner(LowStockIndex::new(restaurant));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> RestaurantUpdate<'info> {
+    pub fn update(
+        &mut self,
+        reference: Pubkey,
+        name: String,
+        symbol: String,
+        currency: Pubkey,
+        url: String,
+        bumps: RestaurantUpdateBumps,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Restaurant Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_MANAGE_RESTAURANT.
+            - The new currency must be listed by the protocol.
+            - The currency can't change while orders are pending, their payment is escrowed in the current one.
+            - The currency can't change while the Vault of the current one holds settled funds or undistributed
+            tips, payroll, tips and withdrawals only reach the vault of the restaurant currency.
+
+            What these Instructions do:
+            - Updates the restaurant profile, the account is realloced to fit the new strings.
+            - Keeps the name, symbol and uri of the collection mint metadata in sync.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_RESTAURANT)?;
+        require!(self.currency_listing.listed, ProtocolError::CurrencyNotListed);
+        require!(
+            currency == self.restaurant.currency || self.restaurant.pending_orders == 0,
+            ProtocolError::PendingOrders
+        );
+        if currency != self.restaurant.currency && self.vault.owner == &crate::ID {
+            let vault = Vault::try_deserialize(&mut &self.vault.try_borrow_data()?[..])?;
+            let tip_pool = self.tip_pool.as_ref().ok_or(ProtocolError::InvalidVault)?;
+            require!(
+                vault.escrowed == 0 && vault.settled == 0 && tip_pool.is_empty(),
+                ProtocolError::VaultNotEmpty
+            );
+        }
+
+        // top up the mint so the token program can realloc the metadata
+        let mint_space = {
+            let mint_data = self.mint.try_borrow_data()?;
+            let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+            let mut metadata = mint.get_variable_len_extension::<TokenMetadata>()?;
+            let old_size = metadata.tlv_size_of()?;
+
+            metadata.name = name.clone();
+            metadata.symbol = symbol.clone();
+            metadata.uri = url.clone();
+
+            (mint_data.len() + metadata.tlv_size_of()?).saturating_sub(old_size)
+        };
+
+        let lamports = Rent::get()?.minimum_balance(mint_space).saturating_sub(self.mint.lamports());
+        if lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.authority.to_account_info(),
+                        to: self.mint.to_account_info(),
+                    }
+                ),
+                lamports,
+            )?;
+        }
+
+        let seeds: &[&[u8]; 2] = &[
+            b"auth",
+            &[bumps.auth],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        for (field, value) in [(Field::Name, &name), (Field::Symbol, &symbol), (Field::Uri, &url)] {
+            invoke_signed(
+                &update_metadata_account(
+                    &self.token_2022_program.key(),
+                    &self.mint.key(),
+                    &self.auth.key(),
+                    field,
+                    value.to_string(),
+                ),
+                &[
+                    self.mint.to_account_info(),
+                    self.auth.to_account_info(),
+                ],
+                signer_seeds
+            )?;
+        }
+
+        self.restaurant.reference = reference;
+        self.restaurant.name = name;
+        self.restaurant.symbol = symbol;
+        self.restaurant.currency = currency;
+        self.restaurant.url = url;
+
+        emit!(RestaurantUpdated {
+            restaurant: self.restaurant.key(),
+            reference,
+            name: self.restaurant.name.clone(),
+            symbol: self.restaurant.symbol.clone(),
+            currency,
+            url: self.restaurant.url.clone(),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RestaurantOwnerPropose<'info> {
+    pub fn propose_owner(
+        &mut self,
+        new_owner: Option<Pubkey>,
+    ) -> Result<()> {
+
+        /*
+        
+            Propose Restaurant Owner Ix:
+
+            Some security check:
+            - The signer must be the current restaurant owner.
+
+            What these Instructions do:
+            - Records the proposed new owner, passing None cancels a pending transfer.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        require!(self.owner.key() == self.restaurant.owner, ProtocolError::UnauthorizedAdmin);
+
+        self.restaurant.pending_owner = new_owner;
+
+        Ok(())
+    }
+}
+
+impl<'info> RestaurantOwnerAccept<'info> {
+    pub fn accept_owner(&mut self) -> Result<()> {
+
+        /*
+        
+            Accept Restaurant Owner Ix:
+
+            Some security check:
+            - The signer must be the pending owner proposed by the current owner.
+
+            What these Instructions do:
+            - Makes the signer the restaurant owner. The PDA stays derived from Restaurant.seed and order
+            payments follow Restaurant.owner, so nothing else has to move.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        require!(self.restaurant.pending_owner == Some(self.new_owner.key()), ProtocolError::UnauthorizedAdmin);
+
+        let previous_owner = self.restaurant.owner;
+        self.restaurant.owner = self.new_owner.key();
+        self.restaurant.pending_owner = None;
+
+        emit!(RestaurantOwnerTransferred {
+            restaurant: self.restaurant.key(),
+            previous_owner,
+            owner: self.restaurant.owner,
+        });
+
         Ok(())
     }
 }
 
-impl<'info>RestaurantClose <'info> {
-    pub fn close(&mut self) -> Result<()> {
+impl<'info> RestaurantFeeUpdate<'info> {
+    pub fn update_fee(
+        &mut self,
+        fee_bps: Option<u16>,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Restaurant Fee Ix:
+
+            Some security check:
+            - The admin_state.publickey must match the signing admin.
+            - The override can only lower the fee below Protocol.fee_bps, raising it is left to governance.
+
+            What these Instructions do:
+            - Overrides the protocol fee for this restaurant, passing None falls back to Protocol.fee_bps.
+        */
+
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
         require!(self.admin_state.publickey == *self.admin.key, ProtocolError::UnauthorizedAdmin);
+        self.protocol.require_active(self.admin_state.initialized)?;
+        require!(fee_bps.unwrap_or(0) <= self.protocol.fee_bps, ProtocolError::InvalidFee);
+
+        self.restaurant.protocol_fee_bps = fee_bps;
+
+        Ok(())
+    }
+}
+
+impl<'info> RestaurantPause<'info> {
+    pub fn set_paused(
+        &mut self,
+        paused: bool,
+    ) -> Result<()> {
+
+        /*
+        
+            Pause Restaurant Ix:
 
+            Some security check:
+            - The signer must be the restaurant owner or a protocol admin (admin_state).
+
+            What these Instructions do:
+            - Freezes/unfreezes this restaurant without touching the rest of the protocol.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+
+        if let Some(admin_state) = &self.admin_state {
+            self.protocol.require_active(admin_state.initialized)?;
+        } else {
+            require!(self.signer.key() == self.restaurant.owner, ProtocolError::UnauthorizedAdmin);
+        }
+
+        self.restaurant.paused = paused;
+
+        Ok(())
+    }
+}
+
+impl<'info> RestaurantClose<'info> {
+    pub fn close(
+        &mut self,
+        children: &[AccountInfo<'info>],
+        bumps: RestaurantCloseBumps,
+    ) -> Result<()> {
+
+        /*
+        
+            Close Restaurant Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a protocol admin (admin_state).
+            - The restaurant can't have pending orders.
+            - Once it took orders, the Vault of the restaurant currency can't hold escrowed payments and its TipPool
+            has to be distributed.
+            - Every remaining account must be a staff member, paid shift, timesheet without an open shift, inventory
+            item, menu item, reward, table or non-pending order of this restaurant.
+            - Staff tombstones are kept, they record the terminations.
+            - A brand location has to pass its brand.
+
+            What these Instructions do:
+            - Closes the remaining accounts, the loyalty config, the schedule, the tax accounts and the restaurant
+            back to the signer.
+            - Sweeps the settled funds of the Vault to the owner and closes the Vault, its token account and the TipPool.
+            - Closes the collection mint with the auth PDA.
+            - Takes the restaurant out of its brand.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+
+        if let Some(admin_state) = &self.admin_state {
+            self.protocol.require_active(admin_state.initialized)?;
+        } else {
+            require!(self.signer.key() == self.restaurant.owner, ProtocolError::UnauthorizedAdmin);
+        }
+
+        require!(self.restaurant.pending_orders == 0, ProtocolError::PendingOrders);
+
+        if let Some(brand_key) = self.restaurant.brand {
+            let brand = self.brand.as_mut().ok_or(ProtocolError::InvalidBrand)?;
+            require!(brand.key() == brand_key, ProtocolError::InvalidBrand);
+
+            brand.location_count = brand.location_count.checked_sub(1).ok_or(ProtocolError::CountOverflow)?;
+        }
+
+        // the vault only exists once the restaurant took an order in its currency
+        if self.vault.owner == &crate::ID {
+            let vault = Vault::try_deserialize(&mut &self.vault.try_borrow_data()?[..])?;
+            let tip_pool = self.tip_pool.as_ref().ok_or(ProtocolError::InvalidVault)?;
+            let vault_tokens = self.vault_tokens.as_ref().ok_or(ProtocolError::InvalidVault)?;
+            let owner_currency_ata = self.owner_currency_ata.as_ref().ok_or(ProtocolError::InvalidVault)?;
+            require!(vault.escrowed == 0 && tip_pool.is_empty(), ProtocolError::VaultNotEmpty);
+
+            let restaurant = self.restaurant.key();
+            let seeds: &[&[u8]; 4] = &[
+                b"vault",
+                restaurant.as_ref(),
+                self.restaurant.currency.as_ref(),
+                &[bumps.vault],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            // settled payments and anything sent to the vault directly belong to the owner
+            if vault_tokens.amount > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token::Transfer {
+                            from: vault_tokens.to_account_info(),
+                            to: owner_currency_ata.to_account_info(),
+                            authority: self.vault.to_account_info(),
+                        },
+                        signer_seeds
+                    ),
+                    vault_tokens.amount,
+                )?;
+            }
+
+            token::close_account(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::CloseAccount {
+                        account: vault_tokens.to_account_info(),
+                        destination: self.signer.to_account_info(),
+                        authority: self.vault.to_account_info(),
+                    },
+                    signer_seeds
+                ),
+            )?;
+
+            let vault = self.vault.to_account_info();
+            **self.signer.lamports.borrow_mut() += vault.lamports();
+            **vault.lamports.borrow_mut() = 0;
+            vault.assign(&System::id());
+            vault.realloc(0, false)?;
+        }
+
+        for child in children {
+            require!(child.owner == &crate::ID && child.is_writable, ProtocolError::InvalidChildAccount);
+
+            let restaurant = {
+                let data = child.try_borrow_data()?;
+                let discriminator: [u8; 8] = data
+                    .get(..8)
+                    .and_then(|discriminator| discriminator.try_into().ok())
+                    .ok_or(ProtocolError::InvalidChildAccount)?;
+
+                match discriminator {
+                    StaffMember::DISCRIMINATOR => StaffMember::try_deserialize(&mut &data[..])?.restaurant,
+                    Shift::DISCRIMINATOR => {
+                        let shift = Shift::try_deserialize(&mut &data[..])?;
+                        require!(shift.paid, ProtocolError::UnpaidShifts);
+                        shift.restaurant
+                    },
+                    Timesheet::DISCRIMINATOR => {
+                        let timesheet = Timesheet::try_deserialize(&mut &data[..])?;
+                        require!(timesheet.open_shift.is_none(), ProtocolError::UnpaidShifts);
+                        timesheet.restaurant
+                    },
+                    InventoryItem::DISCRIMINATOR => InventoryItem::try_deserialize(&mut &data[..])?.restaurant,
+                    MenuItem::DISCRIMINATOR => MenuItem::try_deserialize(&mut &data[..])?.restaurant,
+                    Reward::DISCRIMINATOR => Reward::try_deserialize(&mut &data[..])?.restaurant,
+                    Table::DISCRIMINATOR => Table::try_deserialize(&mut &data[..])?.restaurant,
+                    CustomerOrder::DISCRIMINATOR => {
+                        let order = CustomerOrder::try_deserialize(&mut &data[..])?;
+                        require!(!order.is_open(), ProtocolError::PendingOrders);
+                        order.restaurant
+                    },
+                    _ => return err!(ProtocolError::InvalidChildAccount),
+                }
+            };
+            require!(restaurant == self.restaurant.key(), ProtocolError::InvalidChildAccount);
+
+            // same teardown as Anchor's `close` constraint
+            let lamports = child.lamports();
+            **self.signer.lamports.borrow_mut() += lamports;
+            **child.lamports.borrow_mut() = 0;
+            child.assign(&System::id());
+            child.realloc(0, false)?;
+        }
+
+        let seeds: &[&[u8]; 2] = &[
+            b"auth",
+            &[bumps.auth],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        invoke_signed(
+            &close_account(
+                &self.token_2022_program.key(),
+                &self.mint.key(),
+                &self.signer.key(),
+                &self.auth.key(),
+                &[],
+            )?,
+            &[
+                self.mint.to_account_info(),
+                self.signer.to_account_info(),
+                self.auth.to_account_info(),
+            ],
+            signer_seeds
+        )?;
 
         Ok(())
     }
@@ -118,7 +701,7 @@ impl<'info>RestaurantClose <'info> {
     reference: Pubkey,
     name: String,
     symbol: String,
-    owner: Pubkey,
+    currency: Pubkey,
     url: String,
 )]
 pub struct RestaurantInit<'info> {
@@ -140,6 +723,46 @@ pub struct RestaurantInit<'info> {
         space = Restaurant::INIT_SPACE + 54 + url.len() + name.len() + symbol.len() + 4 + 4
     )] 
     pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        init,
+        seeds = [b"loyalty", restaurant.key().as_ref()],
+        bump,
+        payer = admin,
+        space = LoyaltyConfig::INIT_SPACE
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+    #[account(
+        init,
+        seeds = [b"schedule", restaurant.key().as_ref()],
+        bump,
+        payer = admin,
+        space = Schedule::INIT_SPACE
+    )]
+    pub schedule: Account<'info, Schedule>,
+    #[account(
+        init,
+        seeds = [b"tax", restaurant.key().as_ref()],
+        bump,
+        payer = admin,
+        space = TaxConfig::INIT_SPACE
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+    #[account(
+        init,
+        seeds = [b"tax_ledger", restaurant.key().as_ref()],
+        bump,
+        payer = admin,
+        space = TaxLedger::INIT_SPACE
+    )]
+    pub tax_ledger: Account<'info, TaxLedger>,
+    #[account(
+        init,
+        seeds = [b"low_stock", restaurant.key().as_ref()],
+        bump,
+        payer = admin,
+        space = LowStockIndex::INIT_SPACE
+    )]
+    pub low_stock_index: Account<'info, LowStockIndex>,
     /// CHECK: this is fine since we are handling all the checks and creation in the program.
     #[account(
         mut,
@@ -147,6 +770,17 @@ pub struct RestaurantInit<'info> {
         bump
     )]
     pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"auth"],
+        bump
+    )]
+    /// CHECK: mint and close authority of the collection mint
+    pub auth: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"currency", currency.as_ref()],
+        bump
+    )]
+    pub currency_listing: Account<'info, CurrencyListing>,
     #[account(address = RENT_ID)]
     /// CHECK: this is fine since we are hard coding the rent sysvar.
     pub rent: UncheckedAccount<'info>,
@@ -160,28 +794,293 @@ pub struct RestaurantInit<'info> {
 }
 
 #[derive(Accounts)]
-pub struct RestaurantClose<'info> {
+pub struct RestaurantAccountsInit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        init_if_needed,
+        seeds = [b"loyalty", restaurant.key().as_ref()],
+        bump,
+        payer = payer,
+        space = LoyaltyConfig::INIT_SPACE
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+    #[account(
+        init_if_needed,
+        seeds = [b"schedule", restaurant.key().as_ref()],
+        bump,
+        payer = payer,
+        space = Schedule::INIT_SPACE
+    )]
+    pub schedule: Account<'info, Schedule>,
+    #[account(
+        init_if_needed,
+        seeds = [b"tax", restaurant.key().as_ref()],
+        bump,
+        payer = payer,
+        space = TaxConfig::INIT_SPACE
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+    #[account(
+        init_if_needed,
+        seeds = [b"tax_ledger", restaurant.key().as_ref()],
+        bump,
+        payer = payer,
+        space = TaxLedger::INIT_SPACE
+    )]
+    pub tax_ledger: Account<'info, TaxLedger>,
+    #[account(
+        init_if_needed,
+        seeds = [b"low_stock", restaurant.key().as_ref()],
+        bump,
+        payer = payer,
+        space = LowStockIndex::INIT_SPACE
+    )]
+    pub low_stock_index: Account<'info, LowStockIndex>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    reference: Pubkey,
+    name: String,
+    symbol: String,
+    currency: Pubkey,
+    url: String,
+)]
+pub struct RestaurantUpdate<'info> {
     #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
+        bump,
+        realloc = Restaurant::INIT_SPACE + 54 + url.len() + name.len() + symbol.len() + 4 + 4,
+        realloc::payer = authority,
+        realloc::zero = false,
+    )] 
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"currency", currency.as_ref()],
+        bump
+    )]
+    pub currency_listing: Account<'info, CurrencyListing>,
+    /// CHECK: Vault of the current currency, only initialized once the restaurant took an order
+    #[account(
+        seeds = [b"vault", restaurant.key().as_ref(), restaurant.currency.as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: Option<Account<'info, TipPool>>,
+    /// CHECK: the collection mint, its metadata is updated through the token program
+    #[account(
+        mut,
+        seeds = [b"mint", restaurant.key().as_ref()],
+        bump
+    )]
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"auth"],
+        bump
+    )]
+    /// CHECK: update authority of the collection mint metadata
+    pub auth: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RestaurantOwnerPropose<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
+        bump,
+    )] 
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct RestaurantOwnerAccept<'info> {
+    pub new_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
+        bump,
+    )] 
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct RestaurantFeeUpdate<'info> {
     pub admin: Signer<'info>,
     #[account(
         seeds = [b"admin_state", admin.key().as_ref()],
         bump
     )]
     pub admin_state: Account<'info, Admin>,
-    /// CHECK: this is ok because admin is setting up on owner behalf
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
+        bump,
+    )] 
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct RestaurantPause<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"admin_state", signer.key().as_ref()],
+        bump
+    )]
+    pub admin_state: Option<Account<'info, Admin>>,
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
+        bump,
+    )] 
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct RestaurantClose<'info> {
     #[account(mut)]
-    pub owner: AccountInfo<'info>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"admin_state", signer.key().as_ref()],
+        bump
+    )]
+    pub admin_state: Option<Account<'info, Admin>>,
     #[account(
         mut,
-        close = admin,
-        seeds = [b"restaurant", owner.key().as_ref()],
+        close = signer,
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
         bump,
     )] 
     pub restaurant: Account<'info, Restaurant>,
+    #[account(mut)]
+    pub brand: Option<Account<'info, Brand>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"loyalty", restaurant.key().as_ref()],
+        bump
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"schedule", restaurant.key().as_ref()],
+        bump
+    )]
+    pub schedule: Account<'info, Schedule>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"tax", restaurant.key().as_ref()],
+        bump
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"tax_ledger", restaurant.key().as_ref()],
+        bump
+    )]
+    pub tax_ledger: Account<'info, TaxLedger>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"low_stock", restaurant.key().as_ref()],
+        bump
+    )]
+    pub low_stock_index: Account<'info, LowStockIndex>,
+    /// CHECK: Vault of the restaurant currency, only initialized once the restaurant took an order
+    #[account(
+        mut,
+        seeds = [b"vault", restaurant.key().as_ref(), restaurant.currency.as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: Option<Account<'info, TipPool>>,
+    #[account(
+        mut,
+        token::mint = restaurant.currency,
+        token::authority = restaurant.owner,
+    )]
+    pub owner_currency_ata: Option<Account<'info, TokenAccount>>,
+    /// CHECK: the collection mint, closed through the token program
+    #[account(
+        mut,
+        seeds = [b"mint", restaurant.key().as_ref()],
+        bump
+    )]
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"auth"],
+        bump
+    )]
+    /// CHECK: mint and close authority of the collection mint
+    pub auth: UncheckedAccount<'info>,
     #[account(
         seeds = [b"protocol"],
         bump,
     )]
     pub protocol: Account<'info, Protocol>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -1,14 +1,17 @@
 use crate::{
+    auth::{authorize, authorize_catalog},
     state::{
-        RestaurantAdmin,
+        Versioned,
+        StaffMember,
         Restaurant,
+        Brand,
         Protocol,
         Reward,
         Customer,
         CustomerNft,
     },
-    constant::{ ED25519_PROGRAM_ID, admin_wallet },
-    errors::ProtocolError,
+    constant::{ ED25519_PROGRAM_ID, admin_wallet, PAUSE_REWARDS, PERMISSION_MANAGE_MENU, PERMISSION_AIRDROP_REWARDS },
+    errors::{SetupError, ProtocolError},
 };
 use std::str::FromStr;
 pub use anchor_lang::{
@@ -16,7 +19,6 @@ pub use anchor_lang::{
         sysvar::{
             rent::ID as RENT_ID,
             instructions::{
-                self,
                 load_current_index_checked,
                 load_instruction_at_checked,
             }
@@ -67,10 +69,14 @@ impl<'info> RewardInit<'info> {
         */
         
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
-        require!(self.restaurant_admin_state.restaurant.key() == self.restaurant.key(), ProtocolError::UnauthorizedAdmin);
+        self.protocol.require_unpaused(PAUSE_REWARDS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        let owner = authorize_catalog(&self.protocol, &self.restaurant, &self.brand, self.authority.key, &self.staff_member, PERMISSION_MANAGE_MENU)?;
+        require!(restaurant == owner, SetupError::Unauthorized);
         
         self.reward.set_inner(
             Reward {
+                version: Reward::VERSION,
                 category,
                 restaurant,
                 reward_points,
@@ -113,14 +119,14 @@ impl<'info> RewardInit<'info> {
 
         invoke_signed(
             &solana_program::system_instruction::create_account(
-                &self.restaurant_admin.key(),
+                &self.authority.key(),
                 &self.mint.key(),
                 lamports,
                 (size).try_into().unwrap(),
                 &spl_token_2022::id(),
             ),
-            &vec![
-                self.restaurant_admin.to_account_info(),
+            &[
+                self.authority.to_account_info(),
                 self.mint.to_account_info(),
             ],
             signer_seeds
@@ -135,7 +141,7 @@ impl<'info> RewardInit<'info> {
                 &self.mint.key(),
                 &self.auth.key(),
             )?,
-            &vec![
+            &[
                 self.mint.to_account_info(),
             ],
         )?;
@@ -147,7 +153,7 @@ impl<'info> RewardInit<'info> {
                 &self.mint.key(),
                 Some(&self.auth.key()),
             )?,
-            &vec![
+            &[
                 self.mint.to_account_info(),
             ],
         )?;
@@ -160,7 +166,7 @@ impl<'info> RewardInit<'info> {
                 Some(self.auth.key()),
                 Some(self.mint.key()),
             )?,
-            &vec![
+            &[
                 self.mint.to_account_info(),
             ],
         )?;
@@ -174,7 +180,7 @@ impl<'info> RewardInit<'info> {
                 None,
                 0,
             )?,
-            &vec![
+            &[
                 self.mint.to_account_info(),
             ],
             signer_seeds
@@ -197,7 +203,7 @@ impl<'info> RewardInit<'info> {
                 metadata.symbol,
                 metadata.uri,
             ),
-            &vec![
+            &[
                 self.mint.to_account_info(),
                 self.auth.to_account_info(),
             ],
@@ -213,7 +219,7 @@ impl<'info> RewardInit<'info> {
                     Field::Key(field),
                     value,
                 ),
-                &vec![
+                &[
                     self.mint.to_account_info(),
                     self.auth.to_account_info(),
                 ],
@@ -228,6 +234,10 @@ impl<'info> RewardInit<'info> {
 impl<'info> RewardRemove<'info> {
     pub fn remove(&mut self) -> Result<()> {
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_REWARDS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        let owner = authorize_catalog(&self.protocol, &self.restaurant, &self.brand, self.authority.key, &self.staff_member, PERMISSION_MANAGE_MENU)?;
+        require!(self.reward.restaurant == owner, SetupError::Unauthorized);
 
         Ok(())
     }
@@ -247,6 +257,8 @@ impl<'info> RewardBuy<'info> {
         */
 
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_REWARDS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
 
         let seeds: &[&[u8]; 2] = &[
             b"auth",
@@ -321,7 +333,9 @@ impl<'info> RewardBuy<'info> {
 
         let cost_of_reward = self.reward.reward_points;
         let current_reward_points = self.customer_nft.reward_points;
-        let new_reward_points = current_reward_points - cost_of_reward;
+        let new_reward_points = current_reward_points
+            .checked_sub(cost_of_reward)
+            .ok_or(ProtocolError::InsufficientPoints)?;
 
         self.customer_nft.reward_points = new_reward_points;
         
@@ -333,7 +347,7 @@ impl<'info> RewardBuy<'info> {
                 Field::Key("reward_points".to_string()),
                 new_reward_points.to_string(),
             ),
-            &vec![
+            &[
                 self.mint.to_account_info(),
                 self.auth.to_account_info(),
             ],
@@ -359,6 +373,9 @@ impl<'info> RewardAirdrop<'info> {
         */
 
         require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_REWARDS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_AIRDROP_REWARDS)?;
 
         // Instruction Check
         let ixs = self.instructions.to_account_info();
@@ -407,7 +424,7 @@ impl<'info> RewardAirdrop<'info> {
                        CpiContext::new(
                            self.token_2022_program.to_account_info(),
                            Create {
-                               payer: self.restaurant_admin.to_account_info(), // payer
+                               payer: self.authority.to_account_info(), // payer
                                associated_token: self.customer_mint_ata.to_account_info(),
                                authority: self.customer.to_account_info(), // owner
                                mint: self.mint.to_account_info(),
@@ -486,24 +503,28 @@ impl<'info> RewardAirdrop<'info> {
 #[instruction(item: Pubkey)]
 pub struct RewardInit<'info> {
     /// CHECK: this is ok because admin is setting up on owner behalf
-    #[account(mut)]
+    #[account(
+        mut,
+        address = restaurant.owner,
+    )]
     pub restaurant_owner: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [b"restaurant", restaurant_owner.key().as_ref()],
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
         bump,
     )] 
     pub restaurant: Account<'info, Restaurant>,
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub brand: Option<Account<'info, Brand>>,
     #[account(
         init,
-        payer = restaurant_admin,
+        payer = authority,
         space = Reward::INIT_SPACE + 5,
         seeds = [b"reward", reward.key().as_ref(), restaurant.key().as_ref()],
         bump
@@ -537,24 +558,28 @@ pub struct RewardInit<'info> {
 #[derive(Accounts)]
 pub struct RewardRemove<'info> {
     /// CHECK: this is ok because admin is setting up on owner behalf
-    #[account(mut)]
+    #[account(
+        mut,
+        address = restaurant.owner,
+    )]
     pub restaurant_owner: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [b"restaurant", restaurant_owner.key().as_ref()],
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
         bump,
     )] 
     pub restaurant: Account<'info, Restaurant>,
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub brand: Option<Account<'info, Brand>>,
     #[account(
         mut,
-        close = restaurant_admin,
+        close = authority,
         seeds = [b"reward", reward.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
@@ -570,11 +595,14 @@ pub struct RewardRemove<'info> {
 #[derive(Accounts)]
 pub struct RewardBuy<'info> {
     /// CHECK: this is ok because admin is setting up on owner behalf
-    #[account(mut)]
+    #[account(
+        mut,
+        address = restaurant.owner,
+    )]
     pub restaurant_owner: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [b"restaurant", restaurant_owner.key().as_ref()],
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
         bump,
     )] 
     pub restaurant: Account<'info, Restaurant>,
@@ -582,19 +610,18 @@ pub struct RewardBuy<'info> {
     pub customer: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"customer", customer.key().as_ref()],
+        seeds = [b"customer", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump
     )]
     pub customer_profile: Account<'info, Customer>,
     #[account(
         mut,
-        seeds = [b"member_nft", customer.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"member_nft", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump,
     )] 
     pub customer_nft: Account<'info, CustomerNft>,
     #[account(
-        seeds = [b"reward", reward.key().as_ref(), restaurant.key().as_ref()],
-        bump
+        constraint = restaurant.offers(&restaurant.key(), &reward.restaurant) @ ProtocolError::InvalidReward,
     )]
     pub reward: Account<'info, Reward>,
     #[account(
@@ -636,39 +663,41 @@ pub struct RewardBuy<'info> {
 #[derive(Accounts)]
 pub struct RewardAirdrop<'info> {
     /// CHECK: this is ok because admin is setting up on owner behalf
-    #[account(mut)]
+    #[account(
+        mut,
+        address = restaurant.owner,
+    )]
     pub restaurant_owner: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [b"restaurant", restaurant_owner.key().as_ref()],
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
         bump,
     )] 
     pub restaurant: Account<'info, Restaurant>,
     #[account(mut)]
-    pub restaurant_admin: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
-        seeds = [b"admin_state", restaurant_admin.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
         bump
     )]
-    pub restaurant_admin_state: Account<'info, RestaurantAdmin>,
-    /// CHECK: this is ok since it is restaurant_admin minting/sending nft
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    /// CHECK: this is ok since it is authority minting/sending nft
     #[account(mut)]
     pub customer: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [b"customer", customer.key().as_ref()],
+        seeds = [b"customer", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump
     )]
     pub customer_profile: Account<'info, Customer>,
     #[account(
         mut,
-        seeds = [b"member_nft", customer.key().as_ref(), restaurant.key().as_ref()],
+        seeds = [b"member_nft", customer.key().as_ref(), restaurant.brand.unwrap_or(restaurant.key()).as_ref()],
         bump,
     )] 
     pub customer_nft: Account<'info, CustomerNft>,
     #[account(
-        seeds = [b"reward", reward.key().as_ref(), restaurant.key().as_ref()],
-        bump
+        constraint = restaurant.offers(&restaurant.key(), &reward.restaurant) @ ProtocolError::InvalidReward,
     )]
     pub reward: Account<'info, Reward>,
     #[account(
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::{
+    auth::authorize,
+    state::{
+        Restaurant,
+        StaffMember,
+        Schedule,
+        OpeningWindow,
+        Daypart,
+        Protocol
+    },
+    constant::PERMISSION_MANAGE_RESTAURANT,
+    errors::ProtocolError,
+};
+
+impl<'info> ScheduleUpdate<'info> {
+    pub fn update(
+        &mut self,
+        utc_offset: i32,
+        windows: Vec<OpeningWindow>,
+        closed_dates: Vec<i64>,
+        dayparts: Vec<Daypart>,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Schedule Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_MANAGE_RESTAURANT.
+            - The windows, closed dates and dayparts must fit in the account and be well formed.
+
+            What these Instructions do:
+            - Replaces the opening hours, holidays and dayparts checked by add_order.
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_RESTAURANT)?;
+        Schedule::validate(utc_offset, &windows, &closed_dates, &dayparts)?;
+
+        self.schedule.utc_offset = utc_offset;
+        self.schedule.windows = windows;
+        self.schedule.closed_dates = closed_dates;
+        self.schedule.dayparts = dayparts;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ScheduleUpdate<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        seeds = [b"schedule", restaurant.key().as_ref()],
+        bump
+    )]
+    pub schedule: Account<'info, Schedule>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
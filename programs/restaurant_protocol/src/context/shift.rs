@@ -0,0 +1,384 @@
+use anchor_lang::prelude::*;
+use crate::{
+    auth::authorize,
+    events::PayPeriodClosed,
+    state::{
+        Versioned,
+        Restaurant,
+        StaffMember,
+        Shift,
+        Timesheet,
+        TipPool,
+        Protocol
+    },
+    constant::{PAUSE_STAFF, PERMISSION_MANAGE_STAFF, MAX_CORRECTED_SHIFT_SECONDS},
+    errors::{SetupError, ProtocolError},
+};
+
+impl<'info> ShiftClockIn<'info> {
+    pub fn clock_in(&mut self) -> Result<()> {
+
+        /*
+        
+            Clock In Ix:
+
+            Some security check:
+            - The signer must be an active (not suspended) staff member of the restaurant.
+            - The staff member can't have another open shift.
+
+            What the Instruction does:
+            - Opens a Shift at the current time and tracks it as the open shift of the Timesheet.
+            - The first clock in creates the Timesheet and starts its pay period.
+            - Records the tip points of the staff member, the shift is weighed with them in the tip pool.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        require!(self.member_state.is_active(), ProtocolError::StaffSuspended);
+        self.protocol.require_active(self.member_state.initialized)?;
+
+        let now = Clock::get()?.unix_timestamp;
+
+        if self.timesheet.version == 0 {
+            self.timesheet.version = Timesheet::VERSION;
+            self.timesheet.restaurant = self.restaurant.key();
+            self.timesheet.member = self.member.key();
+            self.timesheet.period_start = now;
+        }
+        require!(self.timesheet.open_shift.is_none(), ProtocolError::ShiftOpen);
+
+        self.shift.set_inner(Shift {
+            version: Shift::VERSION,
+            restaurant: self.restaurant.key(),
+            member: self.member.key(),
+            shift_id: self.timesheet.shift_count,
+            clock_in: now,
+            clock_out: 0,
+            corrected_by: None,
+            paid: false,
+        });
+
+        self.timesheet.tip_points = self.member_state.tip_points;
+        self.timesheet.shift_count = self.timesheet.shift_count.checked_add(1).ok_or(ProtocolError::CountOverflow)?;
+        self.timesheet.open_shift = Some(self.shift.key());
+
+        Ok(())
+    }
+}
+
+impl<'info> ShiftClockOut<'info> {
+    pub fn clock_out(&mut self) -> Result<()> {
+
+        /*
+        
+            Clock Out Ix:
+
+            Some security check:
+            - The signer must be the staff member clocked in to the shift.
+
+            What the Instruction does:
+            - Finalizes the open Shift at the current time and rolls it into the current pay period.
+            - Adds the shift to the tip time of the current tip period.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        require!(self.timesheet.open_shift == Some(self.shift.key()), ProtocolError::InvalidShift);
+
+        self.shift.clock_out = Clock::get()?.unix_timestamp;
+
+        self.timesheet.open_shift = None;
+        self.timesheet.period_seconds = self.timesheet.period_seconds.checked_add(self.shift.seconds()).ok_or(ProtocolError::CountOverflow)?;
+        self.timesheet.period_shifts = self.timesheet.period_shifts.checked_add(1).ok_or(ProtocolError::CountOverflow)?;
+        add_tip_time(&self.tip_pool, &mut self.timesheet, self.shift.seconds())?;
+
+        Ok(())
+    }
+}
+
+impl<'info> ShiftCorrect<'info> {
+    pub fn correct(
+        &mut self,
+        clock_in: i64,
+        clock_out: i64,
+    ) -> Result<()> {
+
+        /*
+        
+            Correct Shift Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the MANAGE_STAFF permission.
+            - Only open shifts and unpaid shifts of the current pay period can be corrected.
+            - The corrected shift must end after it starts, can't end in the future and can't be longer than
+            MAX_CORRECTED_SHIFT_SECONDS.
+            - Staff can't correct their own shifts, only the owner can.
+
+            What the Instruction does:
+            - Replaces the clock in / clock out times of the shift, an open shift is finalized.
+            - Moves the pay period summary of the Timesheet by the difference.
+            - A finalized open shift is added to the tip time of the current tip period, the tip time of a shift
+            clocked out before is kept.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_STAFF)?;
+        require!(
+            self.member.key() != self.authority.key() || self.authority.key() == self.restaurant.owner,
+            SetupError::Unauthorized
+        );
+        require!(clock_in < clock_out && clock_out <= Clock::get()?.unix_timestamp, ProtocolError::InvalidShift);
+        require!(clock_out - clock_in <= MAX_CORRECTED_SHIFT_SECONDS, ProtocolError::InvalidShift);
+        require!(!self.shift.paid, ProtocolError::InvalidShift);
+
+        let finalized = self.timesheet.open_shift == Some(self.shift.key());
+        if finalized {
+            self.timesheet.open_shift = None;
+            self.timesheet.period_shifts = self.timesheet.period_shifts.checked_add(1).ok_or(ProtocolError::CountOverflow)?;
+        } else {
+            require!(
+                self.shift.clock_out != 0 && self.shift.clock_out >= self.timesheet.period_start,
+                ProtocolError::InvalidShift
+            );
+            self.timesheet.period_seconds = self.timesheet.period_seconds.checked_sub(self.shift.seconds()).ok_or(ProtocolError::CountOverflow)?;
+        }
+
+        self.shift.clock_in = clock_in;
+        self.shift.clock_out = clock_out;
+        self.shift.corrected_by = Some(self.authority.key());
+
+        self.timesheet.period_seconds = self.timesheet.period_seconds.checked_add(self.shift.seconds()).ok_or(ProtocolError::CountOverflow)?;
+
+        if finalized {
+            add_tip_time(&self.tip_pool, &mut self.timesheet, self.shift.seconds())?;
+        }
+
+        Ok(())
+    }
+}
+
+// A shift counts towards the tip period it is clocked out in, weighed with the tip points of its clock in. The
+// TipPool only exists once the restaurant took an order, shifts clocked out before don't share any tips.
+fn add_tip_time(tip_pool: &AccountInfo, timesheet: &mut Timesheet, seconds: u64) -> Result<()> {
+    if tip_pool.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let mut data = tip_pool.try_borrow_mut_data()?;
+    let mut pool = TipPool::try_deserialize(&mut &data[..])?;
+
+    if timesheet.tip_period != pool.period_start {
+        // the tip time of the closed period being paid out waits for its payout, the one before was paid already
+        if timesheet.tip_period != 0 && timesheet.tip_period == pool.payout_start {
+            timesheet.unpaid_tip_period = timesheet.tip_period;
+            timesheet.unpaid_tip_seconds = timesheet.tip_seconds;
+            timesheet.unpaid_tip_weight = timesheet.tip_weight;
+        }
+
+        timesheet.tip_period = pool.period_start;
+        timesheet.tip_seconds = 0;
+        timesheet.tip_weight = 0;
+        pool.period_members = pool.period_members.checked_add(1).ok_or(ProtocolError::CountOverflow)?;
+    }
+
+    let weight = seconds.checked_mul(timesheet.tip_points as u64).ok_or(ProtocolError::CountOverflow)?;
+    timesheet.tip_seconds = timesheet.tip_seconds.checked_add(seconds).ok_or(ProtocolError::CountOverflow)?;
+    timesheet.tip_weight = timesheet.tip_weight.checked_add(weight).ok_or(ProtocolError::CountOverflow)?;
+    pool.period_seconds = pool.period_seconds.checked_add(seconds).ok_or(ProtocolError::CountOverflow)?;
+    pool.period_weight = pool.period_weight.checked_add(weight).ok_or(ProtocolError::CountOverflow)?;
+
+    pool.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+impl<'info> PayPeriodClose<'info> {
+    pub fn close_period(&mut self) -> Result<()> {
+
+        /*
+        
+            Close Pay Period Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the MANAGE_STAFF permission.
+
+            What the Instruction does:
+            - Reports the time worked and tips received since the pay period started in a PayPeriodClosed event and keeps it as
+            the last period of the Timesheet.
+            - Starts a new pay period, a shift still open is rolled into the new period when it is clocked out.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_STAFF)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let timesheet = &mut self.timesheet;
+
+        emit!(PayPeriodClosed {
+            restaurant: self.restaurant.key(),
+            member: timesheet.member,
+            period_start: timesheet.period_start,
+            period_end: now,
+            seconds: timesheet.period_seconds,
+            shifts: timesheet.period_shifts,
+            tips: timesheet.period_tips,
+        });
+
+        timesheet.last_period_start = timesheet.period_start;
+        timesheet.last_period_end = now;
+        timesheet.last_period_seconds = timesheet.period_seconds;
+        timesheet.last_period_shifts = timesheet.period_shifts;
+        timesheet.last_period_tips = timesheet.period_tips;
+        timesheet.period_start = now;
+        timesheet.period_seconds = 0;
+        timesheet.period_shifts = 0;
+        timesheet.period_tips = 0;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ShiftClockIn<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"staff", member.key().as_ref(), restaurant.key().as_ref()],
+        bump,
+        constraint = member_state.restaurant == restaurant.key() @ SetupError::Unauthorized,
+    )]
+    pub member_state: Account<'info, StaffMember>,
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = Timesheet::INIT_SPACE,
+        seeds = [b"timesheet", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub timesheet: Account<'info, Timesheet>,
+    #[account(
+        init,
+        payer = member,
+        space = Shift::INIT_SPACE,
+        seeds = [b"shift", timesheet.key().as_ref(), timesheet.shift_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub shift: Account<'info, Shift>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ShiftClockOut<'info> {
+    pub member: Signer<'info>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        seeds = [b"timesheet", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub timesheet: Account<'info, Timesheet>,
+    #[account(
+        mut,
+        seeds = [b"shift", timesheet.key().as_ref(), shift.shift_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub shift: Account<'info, Shift>,
+    /// CHECK: Vault of the restaurant currency, it doesn't exist before the first order
+    #[account(
+        seeds = [b"vault", restaurant.key().as_ref(), restaurant.currency.as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: TipPool of the vault, deserialized in the program when it exists
+    #[account(
+        mut,
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct ShiftCorrect<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    /// CHECK: This is the staff member whose shift is corrected, only used as a seed
+    pub member: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"timesheet", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub timesheet: Account<'info, Timesheet>,
+    #[account(
+        mut,
+        seeds = [b"shift", timesheet.key().as_ref(), shift.shift_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub shift: Account<'info, Shift>,
+    /// CHECK: Vault of the restaurant currency, it doesn't exist before the first order
+    #[account(
+        seeds = [b"vault", restaurant.key().as_ref(), restaurant.currency.as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: TipPool of the vault, deserialized in the program when it exists
+    #[account(
+        mut,
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct PayPeriodClose<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    /// CHECK: This is the staff member whose pay period is closed, only used as a seed
+    pub member: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"timesheet", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub timesheet: Account<'info, Timesheet>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
@@ -0,0 +1,330 @@
+use anchor_lang::prelude::*;
+use crate::{
+    auth::authorize,
+    state::{
+        Versioned,
+        Restaurant,
+        StaffMember,
+        StaffTombstone,
+        Protocol
+    },
+    constant::{
+        PAUSE_STAFF,
+        PERMISSION_ALL,
+        PERMISSION_MANAGE_STAFF,
+        ROLE_NONE,
+        ROLE_MANAGER,
+        STAFF_ACTIVE,
+        STAFF_SUSPENDED,
+    },
+    errors::{SetupError, ProtocolError},
+};
+
+// A staff member with MANAGE_STAFF can only hand out (or take away) permissions it holds itself, and
+// a staff member limited by a role can only manage members of the same role.
+fn require_grantable(
+    restaurant: &Account<Restaurant>,
+    authority: &Pubkey,
+    staff_member: &Option<Account<StaffMember>>,
+    permissions: u16,
+    role: u8,
+) -> Result<()> {
+    require!(permissions & !PERMISSION_ALL == 0, ProtocolError::InvalidPermissions);
+    require!(role <= ROLE_MANAGER, ProtocolError::InvalidRole);
+
+    if *authority != restaurant.owner {
+        let staff_member = staff_member.as_ref().ok_or(SetupError::Unauthorized)?;
+        require!(staff_member.has_permission(permissions), SetupError::Unauthorized);
+        require!(
+            matches!(staff_member.role, ROLE_NONE | ROLE_MANAGER) || staff_member.role == role,
+            SetupError::Unauthorized
+        );
+    }
+
+    Ok(())
+}
+
+impl<'info> StaffInit<'info> {
+    pub fn add(
+        &mut self,
+        username: String,
+        permissions: u16,
+        hourly_wage: u64,
+        tip_points: u16,
+        role: u8,
+    ) -> Result<()> {
+
+        /*
+        
+            Add Staff Member Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the MANAGE_STAFF permission.
+            - A staff member can't grant permissions it doesn't hold itself, nor a role other than its own
+            when it is limited by one.
+
+            What the Instruction does:
+            - Creates the StaffMember record of the wallet for this restaurant with its permissions, role,
+            hourly wage and tip points.
+            - Save the Time of initialization, the record is useless until Protocol.admin_cooldown has passed.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_STAFF)?;
+        require_grantable(&self.restaurant, self.authority.key, &self.staff_member, permissions, role)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        
+        self.member_state.set_inner(StaffMember {
+            version: StaffMember::VERSION,
+            publickey: self.member.key(),
+            restaurant: self.restaurant.key(),
+            username,
+            permissions,
+            initialized: now,
+            hourly_wage,
+            tip_points,
+            role,
+            status: STAFF_ACTIVE,
+            status_changed_at: now,
+            status_reason: 0,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> StaffUpdate<'info> {
+    pub fn update(
+        &mut self,
+        permissions: u16,
+        hourly_wage: u64,
+        tip_points: u16,
+        role: u8,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Staff Member Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the MANAGE_STAFF permission.
+            - A staff member can only change the permissions of members it outranks, and only to permissions it holds.
+            - Only the owner can change its own record, staff can't raise their own wage or tip points.
+
+            What the Instruction does:
+            - Replaces the permissions, the role, the hourly wage and the tip points of the staff member.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_STAFF)?;
+        require_grantable(&self.restaurant, self.authority.key, &self.staff_member, self.member_state.permissions, self.member_state.role)?;
+        require_grantable(&self.restaurant, self.authority.key, &self.staff_member, permissions, role)?;
+        require!(
+            self.member.key() != self.authority.key() || self.authority.key() == self.restaurant.owner,
+            SetupError::Unauthorized
+        );
+
+        self.member_state.permissions = permissions;
+        self.member_state.role = role;
+        self.member_state.hourly_wage = hourly_wage;
+        self.member_state.tip_points = tip_points;
+
+        Ok(())
+    }
+}
+
+impl<'info> StaffStatusUpdate<'info> {
+    pub fn update_status(
+        &mut self,
+        status: u8,
+        reason: u8,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Staff Status Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the MANAGE_STAFF permission.
+            - A staff member can only suspend or reactivate members it outranks, and not itself.
+
+            What the Instruction does:
+            - Suspends (STAFF_SUSPENDED) or reactivates (STAFF_ACTIVE) the staff member with a reason code,
+            suspended staff are rejected by every staff-gated instruction.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_STAFF)?;
+        require_grantable(&self.restaurant, self.authority.key, &self.staff_member, self.member_state.permissions, self.member_state.role)?;
+        require!(self.member.key() != self.authority.key(), SetupError::Unauthorized);
+        require!(status == STAFF_ACTIVE || status == STAFF_SUSPENDED, ProtocolError::InvalidStaffStatus);
+
+        self.member_state.status = status;
+        self.member_state.status_changed_at = Clock::get()?.unix_timestamp;
+        self.member_state.status_reason = reason;
+
+        Ok(())
+    }
+}
+
+impl<'info> StaffRemove<'info> {
+    pub fn remove(
+        &mut self,
+        reason: u8,
+    ) -> Result<()> {
+
+        /*
+        
+            Terminate Staff Member Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the MANAGE_STAFF permission.
+            - A staff member can only terminate members it outranks.
+
+            What the Instruction does:
+            - Closes the StaffMember record of the wallet, this also works while the restaurant is paused.
+            - Keeps a StaffTombstone with the hire and termination dates and the reason code for audit.
+            - The tombstone also keeps the wage and tip points, so payroll can still pay the shifts worked before.
+            - Returns the rest of the rent of the record to the signer.
+
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_STAFF)?;
+        require_grantable(&self.restaurant, self.authority.key, &self.staff_member, self.member_state.permissions, self.member_state.role)?;
+
+        self.tombstone.set_inner(StaffTombstone {
+            version: StaffTombstone::VERSION,
+            restaurant: self.restaurant.key(),
+            member: self.member.key(),
+            hired_at: self.member_state.initialized,
+            terminated_at: Clock::get()?.unix_timestamp,
+            reason,
+            terminations: self.tombstone.terminations + 1,
+            hourly_wage: self.member_state.hourly_wage,
+            tip_points: self.member_state.tip_points,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(username: String)]
+pub struct StaffInit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    pub member: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = StaffMember::INIT_SPACE + username.len(),
+        seeds = [b"staff", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub member_state: Account<'info, StaffMember>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StaffUpdate<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    pub member: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"staff", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub member_state: Account<'info, StaffMember>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct StaffStatusUpdate<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    /// CHECK: This is the staff member being suspended or reactivated, only used as a seed
+    pub member: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"staff", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub member_state: Account<'info, StaffMember>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct StaffRemove<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    /// CHECK: This is the staff member being removed, the signer is checked in the program
+    pub member: AccountInfo<'info>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"staff", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub member_state: Account<'info, StaffMember>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = StaffTombstone::INIT_SPACE,
+        seeds = [b"staff_tombstone", member.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, StaffTombstone>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,329 @@
+use anchor_lang::prelude::*;
+use crate::{
+    auth::authorize,
+    state::{
+        Versioned,
+        Restaurant,
+        StaffMember,
+        Table,
+        CustomerOrder,
+        Protocol
+    },
+    constant::{
+        PAUSE_ORDERS,
+        PERMISSION_MANAGE_RESTAURANT,
+        PERMISSION_UPDATE_ORDERS,
+        MAX_TABLE_LABEL_LEN,
+        TABLE_FREE,
+        TABLE_SEATED,
+        TABLE_DIRTY,
+    },
+    errors::ProtocolError,
+};
+
+impl<'info> TableInit<'info> {
+    pub fn add(
+        &mut self,
+        table_id: u16,
+        label: String,
+        capacity: u8,
+    ) -> Result<()> {
+
+        /*
+
+            Create Table Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_MANAGE_RESTAURANT.
+
+            What these Instructions do:
+            - Creates a free dine-in table for the restaurant.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_RESTAURANT)?;
+        require!(label.len() <= MAX_TABLE_LABEL_LEN && capacity > 0, ProtocolError::InvalidTable);
+
+        self.table.set_inner(Table {
+            version: Table::VERSION,
+            restaurant: self.restaurant.key(),
+            table_id,
+            label,
+            capacity,
+            status: TABLE_FREE,
+            party_size: 0,
+            seated_at: 0,
+            open_orders: Vec::new(),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> TableRemove<'info> {
+    pub fn remove(&mut self) -> Result<()> {
+
+        /*
+
+            Remove Table Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_MANAGE_RESTAURANT.
+            - The table must be free.
+
+            What these Instructions do:
+            - Closes the table account back to the authority.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_RESTAURANT)?;
+        require!(self.table.status == TABLE_FREE, ProtocolError::InvalidTableStatus);
+
+        Ok(())
+    }
+}
+
+impl<'info> TableSeat<'info> {
+    pub fn seat(
+        &mut self,
+        party_size: u8,
+    ) -> Result<()> {
+
+        /*
+
+            Seat Table Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_UPDATE_ORDERS.
+            - The table must be free and fit the party.
+
+            What these Instructions do:
+            - Seats a party at the table, dine-in orders can now be bound to it.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_UPDATE_ORDERS)?;
+        require!(self.table.status == TABLE_FREE, ProtocolError::InvalidTableStatus);
+        require!(party_size > 0 && party_size <= self.table.capacity, ProtocolError::InvalidTable);
+
+        self.table.status = TABLE_SEATED;
+        self.table.party_size = party_size;
+        self.table.seated_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
+
+impl<'info> TableTransfer<'info> {
+    pub fn transfer(
+        &mut self,
+        orders: &[AccountInfo<'info>],
+    ) -> Result<()> {
+
+        /*
+
+            Transfer Table Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_UPDATE_ORDERS.
+            - The party must be seated at `from` and `to` must be free and fit the party.
+            - The remaining accounts must be the open orders of `from`, in order.
+
+            What these Instructions do:
+            - Moves the party and its open orders to `to`, `from` is left dirty.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_UPDATE_ORDERS)?;
+        require!(self.from.key() != self.to.key(), ProtocolError::InvalidTable);
+        require!(self.from.status == TABLE_SEATED && self.to.status == TABLE_FREE, ProtocolError::InvalidTableStatus);
+        require!(self.from.party_size <= self.to.capacity, ProtocolError::InvalidTable);
+        require!(orders.len() == self.from.open_orders.len(), ProtocolError::InvalidTable);
+
+        for (open_order, info) in self.from.open_orders.iter().zip(orders) {
+            require!(info.key() == *open_order && info.owner == &crate::ID, ProtocolError::InvalidTable);
+
+            let mut data = info.try_borrow_mut_data()?;
+            let mut order = CustomerOrder::try_deserialize(&mut &data[..])?;
+            order.table = Some(self.to.key());
+            order.try_serialize(&mut &mut data[..])?;
+        }
+
+        self.to.status = TABLE_SEATED;
+        self.to.party_size = self.from.party_size;
+        self.to.seated_at = self.from.seated_at;
+        self.to.open_orders = std::mem::take(&mut self.from.open_orders);
+
+        self.from.status = TABLE_DIRTY;
+        self.from.party_size = 0;
+        self.from.seated_at = 0;
+
+        Ok(())
+    }
+}
+
+impl<'info> TableClear<'info> {
+    pub fn clear(&mut self) -> Result<()> {
+
+        /*
+
+            Clear Table Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_UPDATE_ORDERS.
+            - A seated table can only be cleared once all its orders are closed.
+
+            What these Instructions do:
+            - Moves a seated table to dirty once the party leaves, and a dirty table to free once it is bussed.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_ORDERS)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_UPDATE_ORDERS)?;
+
+        match self.table.status {
+            TABLE_SEATED => {
+                require!(self.table.open_orders.is_empty(), ProtocolError::TableHasOpenOrders);
+                self.table.status = TABLE_DIRTY;
+                self.table.party_size = 0;
+                self.table.seated_at = 0;
+            },
+            TABLE_DIRTY => self.table.status = TABLE_FREE,
+            _ => return err!(ProtocolError::InvalidTableStatus),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(table_id: u16)]
+pub struct TableInit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        init,
+        payer = authority,
+        space = Table::INIT_SPACE,
+        seeds = [b"table", restaurant.key().as_ref(), table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TableRemove<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"table", restaurant.key().as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct TableSeat<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        seeds = [b"table", restaurant.key().as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct TableTransfer<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        seeds = [b"table", restaurant.key().as_ref(), from.table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub from: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"table", restaurant.key().as_ref(), to.table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub to: Account<'info, Table>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct TableClear<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        seeds = [b"table", restaurant.key().as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use crate::{
+    auth::authorize,
+    events::TaxPeriodClosed,
+    state::{
+        Restaurant,
+        StaffMember,
+        TaxConfig,
+        TaxLedger,
+        CategoryTaxRate,
+        Protocol
+    },
+    constant::PERMISSION_MANAGE_RESTAURANT,
+    errors::ProtocolError,
+};
+
+impl<'info> TaxConfigUpdate<'info> {
+    pub fn update(
+        &mut self,
+        default_rate_bps: u16,
+        category_rates: Vec<CategoryTaxRate>,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Tax Config Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_MANAGE_RESTAURANT.
+            - Rates can't exceed 100% and every category can only have one rate.
+
+            What these Instructions do:
+            - Replaces the tax rates add_order applies to each menu item category.
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_RESTAURANT)?;
+        TaxConfig::validate(default_rate_bps, &category_rates)?;
+
+        self.tax_config.default_rate_bps = default_rate_bps;
+        self.tax_config.category_rates = category_rates;
+
+        Ok(())
+    }
+}
+
+impl<'info> TaxPeriodClose<'info> {
+    pub fn close_period(&mut self) -> Result<()> {
+
+        /*
+        
+            Close Tax Period Ix:
+
+            Some security check:
+            - The authority must be the restaurant owner or a staff member with PERMISSION_MANAGE_RESTAURANT.
+
+            What these Instructions do:
+            - Reports the tax collected since the period started in a TaxPeriodClosed event and keeps it as
+            the last period of the ledger.
+            - Starts a new period.
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_RESTAURANT)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let ledger = &mut self.tax_ledger;
+
+        emit!(TaxPeriodClosed {
+            restaurant: self.restaurant.key(),
+            period_start: ledger.period_start,
+            period_end: now,
+            collected: ledger.collected,
+            order_count: ledger.order_count,
+        });
+
+        ledger.last_period_start = ledger.period_start;
+        ledger.last_period_end = now;
+        ledger.last_period_collected = ledger.collected;
+        ledger.last_period_order_count = ledger.order_count;
+        ledger.period_start = now;
+        ledger.collected = 0;
+        ledger.order_count = 0;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct TaxConfigUpdate<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        seeds = [b"tax", restaurant.key().as_ref()],
+        bump
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct TaxPeriodClose<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        mut,
+        seeds = [b"tax_ledger", restaurant.key().as_ref()],
+        bump
+    )]
+    pub tax_ledger: Account<'info, TaxLedger>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
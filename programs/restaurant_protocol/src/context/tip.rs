@@ -0,0 +1,305 @@
+use {
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer},
+};
+use crate::{
+    auth::authorize,
+    events::TipsPaid,
+    state::{
+        Restaurant,
+        StaffMember,
+        Timesheet,
+        Vault,
+        TipPool,
+        Protocol
+    },
+    constant::{PAUSE_STAFF, PERMISSION_MANAGE_STAFF, PERMISSION_RUN_PAYROLL, TIP_SPLIT_HOURS, TIP_SPLIT_POINTS},
+    errors::ProtocolError,
+};
+
+impl<'info> TipPoolUpdate<'info> {
+    pub fn update(
+        &mut self,
+        split: u8,
+    ) -> Result<()> {
+
+        /*
+        
+            Update Tip Pool Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the MANAGE_STAFF permission.
+
+            What these Instructions do:
+            - Chooses how the tips are split: by hours worked, or by hours worked times StaffMember.tip_points.
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_MANAGE_STAFF)?;
+        require!(split == TIP_SPLIT_HOURS || split == TIP_SPLIT_POINTS, ProtocolError::InvalidTipSplit);
+
+        self.tip_pool.split = split;
+
+        Ok(())
+    }
+}
+
+impl<'info> TipPeriodClose<'info> {
+    pub fn close_period(&mut self) -> Result<()> {
+
+        /*
+        
+            Close Tip Period Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the RUN_PAYROLL permission.
+            - The tips of the previous tip period must have reached all its staff.
+
+            What these Instructions do:
+            - Closes the tip period and sets its TipPool balance aside as the payout, split by the tip time its staff
+            clocked out (times their tip points for a TIP_SPLIT_POINTS pool). A pool without tip points splits by
+            hours, one without tip time keeps its tips for the next period.
+            - Starts a new tip period, shifts still open count towards it when they are clocked out.
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_RUN_PAYROLL)?;
+
+        let pool = &mut self.tip_pool;
+        require!(!pool.is_paying_out(), ProtocolError::TipsPayoutPending);
+
+        let now = Clock::get()?.unix_timestamp;
+        // the next period starts now, it must not look like the one being closed
+        require!(now > pool.period_start, ProtocolError::InvalidPayroll);
+
+        pool.payout_by_points = pool.split == TIP_SPLIT_POINTS && pool.period_weight > 0;
+        pool.payout_weight = if pool.payout_by_points { pool.period_weight } else { pool.period_seconds };
+        pool.payout_start = pool.period_start;
+        pool.payout_end = now;
+        pool.payout_members = pool.period_members;
+        pool.payout_paid = 0;
+        pool.payout_amount = if pool.payout_weight > 0 { pool.balance } else { 0 };
+        pool.balance -= pool.payout_amount;
+
+        pool.period_start = now;
+        pool.period_members = 0;
+        pool.period_seconds = 0;
+        pool.period_weight = 0;
+
+        Ok(())
+    }
+}
+
+impl<'info> TipsDistribute<'info> {
+    pub fn distribute(
+        &mut self,
+        accounts: &[AccountInfo<'info>],
+        bumps: TipsDistributeBumps,
+    ) -> Result<()> {
+
+        /*
+        
+            Distribute Tips Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner or a staff member with the RUN_PAYROLL permission.
+            - The remaining accounts hold pairs of a Timesheet and its currency token account, every Timesheet must
+            have tip time in the closed tip period that wasn't paid out yet.
+
+            What these Instructions do:
+            - Pays the staff passed their share of the payout, in as many calls as needed.
+            - Records every payout on the Timesheet pay period and in a TipsPaid event.
+            - Once all staff of the closed period are paid the rounding dust goes back to the pool balance.
+        */
+        
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        self.protocol.require_unpaused(PAUSE_STAFF)?;
+        require!(!self.restaurant.paused, ProtocolError::RestaurantPaused);
+        authorize(&self.protocol, &self.restaurant, self.authority.key, &self.staff_member, PERMISSION_RUN_PAYROLL)?;
+        require!(!accounts.is_empty() && accounts.len().is_multiple_of(2), ProtocolError::InvalidPayroll);
+
+        let restaurant = self.restaurant.key();
+        let seeds: &[&[u8]; 4] = &[
+            b"vault",
+            restaurant.as_ref(),
+            self.restaurant.currency.as_ref(),
+            &[bumps.vault],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        for pair in accounts.chunks(2) {
+            let (timesheet_info, destination) = (&pair[0], &pair[1]);
+
+            require!(timesheet_info.owner == &crate::ID && timesheet_info.is_writable, ProtocolError::InvalidPayroll);
+            let mut data = timesheet_info.try_borrow_mut_data()?;
+            let mut timesheet = Timesheet::try_deserialize(&mut &data[..])?;
+            require!(timesheet.restaurant == restaurant, ProtocolError::InvalidPayroll);
+
+            require!(destination.owner == &self.token_program.key(), ProtocolError::InvalidPayroll);
+            let token_account = TokenAccount::try_deserialize(&mut &destination.try_borrow_data()?[..])?;
+            require!(
+                token_account.owner == timesheet.member && token_account.mint == self.currency.key(),
+                ProtocolError::InvalidPayroll
+            );
+
+            // the tip time of the closed period is set aside once the staff member clocks out in the next one,
+            // paying it clears it so nobody is paid twice
+            let pool = &mut self.tip_pool;
+            let (seconds, weight) = if pool.is_paying_out() && timesheet.tip_period == pool.payout_start {
+                let share = (timesheet.tip_seconds, timesheet.tip_weight);
+                timesheet.tip_period = 0;
+                timesheet.tip_seconds = 0;
+                timesheet.tip_weight = 0;
+                share
+            } else if pool.is_paying_out() && timesheet.unpaid_tip_period == pool.payout_start {
+                let share = (timesheet.unpaid_tip_seconds, timesheet.unpaid_tip_weight);
+                timesheet.unpaid_tip_period = 0;
+                timesheet.unpaid_tip_seconds = 0;
+                timesheet.unpaid_tip_weight = 0;
+                share
+            } else {
+                return err!(ProtocolError::InvalidPayroll);
+            };
+
+            let weight = if pool.payout_by_points { weight } else { seconds };
+            // a payout without weight has nothing to split
+            let amount = (pool.payout_amount as u128 * weight as u128)
+                .checked_div(pool.payout_weight as u128)
+                .unwrap_or(0) as u64;
+
+            timesheet.period_tips = timesheet.period_tips.checked_add(amount).ok_or(ProtocolError::MoneyOverflow)?;
+            timesheet.try_serialize(&mut &mut data[..])?;
+
+            pool.payout_members -= 1;
+            pool.payout_paid += amount;
+            pool.distributed += amount;
+
+            if amount > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        Transfer {
+                            from: self.vault_tokens.to_account_info(),
+                            to: destination.clone(),
+                            authority: self.vault.to_account_info(),
+                        },
+                        signer_seeds
+                    ),
+                    amount,
+                )?;
+            }
+
+            emit!(TipsPaid {
+                restaurant,
+                member: timesheet.member,
+                period_start: self.tip_pool.payout_start,
+                period_end: self.tip_pool.payout_end,
+                seconds,
+                amount,
+            });
+        }
+
+        // rounding dust stays in the pool for the next period
+        let pool = &mut self.tip_pool;
+        if !pool.is_paying_out() {
+            pool.balance = pool.balance.checked_add(pool.payout_amount - pool.payout_paid).ok_or(ProtocolError::MoneyOverflow)?;
+            pool.payout_amount = 0;
+            pool.payout_paid = 0;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct TipPoolUpdate<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    pub currency: Account<'info, Mint>,
+    #[account(
+        seeds = [b"vault", restaurant.key().as_ref(), currency.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct TipPeriodClose<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(
+        seeds = [b"vault", restaurant.key().as_ref(), restaurant.currency.as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct TipsDistribute<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"staff", authority.key().as_ref(), restaurant.key().as_ref()],
+        bump
+    )]
+    pub staff_member: Option<Account<'info, StaffMember>>,
+    pub restaurant: Account<'info, Restaurant>,
+    #[account(address = restaurant.currency)]
+    pub currency: Account<'info, Mint>,
+    #[account(
+        seeds = [b"vault", restaurant.key().as_ref(), currency.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"tip_pool", vault.key().as_ref()],
+        bump
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub token_program: Program<'info, Token>,
+}
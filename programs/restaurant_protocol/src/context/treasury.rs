@@ -0,0 +1,104 @@
+use {
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer},
+};
+use crate::{
+    state::{
+        Protocol,
+        Proposal,
+        ProposalAction,
+    },
+    errors::{SetupError, ProtocolError},
+};
+
+impl<'info> TreasuryWithdraw<'info> {
+    pub fn withdraw(
+        &mut self,
+        bumps: TreasuryWithdrawBumps,
+    ) -> Result<()> {
+
+        /*
+        
+            Withdraw Treasury Ix:
+
+            Some security check:
+            - Check if the account signing is a protocol authority executing an approved WithdrawTreasury proposal.
+            - The mint, amount and destination must match the proposal.
+
+            What the Instruction does:
+            - Transfers the protocol fees collected in the treasury of the currency to the destination.
+            - Marks the WithdrawTreasury proposal as executed.
+
+        */
+
+        require!(self.protocol.is_authority(self.authority.key), SetupError::Unauthorized);
+        require!(!self.proposal.executed, SetupError::ProposalExecuted);
+        require!(self.proposal.is_approved(&self.protocol), SetupError::ProposalNotApproved);
+
+        let amount = match self.proposal.action {
+            ProposalAction::WithdrawTreasury { mint, amount, destination } => {
+                require!(
+                    mint == self.currency.key() && destination == self.destination.key(),
+                    SetupError::ProposalActionMismatch
+                );
+                amount
+            }
+            _ => return err!(SetupError::ProposalActionMismatch),
+        };
+        require!(amount <= self.treasury.amount, ProtocolError::InvalidWithdrawal);
+
+        let seeds: &[&[u8]; 2] = &[
+            b"protocol",
+            &[bumps.protocol],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.protocol.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+        )?;
+
+        self.proposal.executed = true;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct TreasuryWithdraw<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub currency: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = currency,
+        token::authority = protocol,
+        seeds = [b"treasury", currency.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = currency,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub token_program: Program<'info, Token>,
+}
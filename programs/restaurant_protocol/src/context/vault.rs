@@ -0,0 +1,99 @@
+use {
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer},
+};
+use crate::{
+    state::{
+        Restaurant,
+        Vault,
+        Protocol
+    },
+    errors::ProtocolError,
+};
+
+impl<'info> VaultWithdraw<'info> {
+    pub fn withdraw(
+        &mut self,
+        amount: u64,
+        bumps: VaultWithdrawBumps,
+    ) -> Result<()> {
+
+        /*
+        
+            Withdraw Vault Ix:
+
+            Some security check:
+            - The signer must be the restaurant owner.
+            - Only settled payments can be withdrawn, escrowed payments still belong to pending orders.
+
+            What these Instructions do:
+            - Transfers settled payments from the Vault of the currency to the owner.
+        */
+
+        require!(!self.protocol.locked, ProtocolError::ProtocolLocked);
+        require!(amount <= self.vault.settled, ProtocolError::InvalidWithdrawal);
+
+        let restaurant = self.restaurant.key();
+        let currency = self.currency.key();
+        let seeds: &[&[u8]; 4] = &[
+            b"vault",
+            restaurant.as_ref(),
+            currency.as_ref(),
+            &[bumps.vault],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.vault_tokens.to_account_info(),
+                    to: self.owner_currency_ata.to_account_info(),
+                    authority: self.vault.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+        )?;
+
+        self.vault.settled -= amount;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct VaultWithdraw<'info> {
+    #[account(address = restaurant.owner)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"restaurant", restaurant.seed.as_ref()],
+        bump,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+    pub currency: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"vault", restaurant.key().as_ref(), currency.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = currency,
+        associated_token::authority = owner,
+    )]
+    pub owner_currency_ata: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+    pub token_program: Program<'info, Token>,
+}
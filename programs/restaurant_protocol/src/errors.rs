@@ -4,6 +4,20 @@ use anchor_lang::error_code;
 pub enum SetupError {
     #[msg("You are not authorized to perform this action")]
     Unauthorized,
+    #[msg("The Protocol has already been initialized")]
+    AlreadyInitialized,
+    #[msg("Invalid authority set")]
+    InvalidAuthorities,
+    #[msg("Invalid approval threshold")]
+    InvalidThreshold,
+    #[msg("Proposal has already been executed")]
+    ProposalExecuted,
+    #[msg("Proposal has already been approved by this authority")]
+    ProposalAlreadyApproved,
+    #[msg("Proposal does not have enough approvals")]
+    ProposalNotApproved,
+    #[msg("Proposal action does not match this instruction")]
+    ProposalActionMismatch,
 }
 #[error_code]
 pub enum ProtocolError {
@@ -31,4 +45,106 @@ pub enum ProtocolError {
     InvalidBalancePreBurn,
     #[msg("Invalid balance post burn")]
     InvalidBalancePostBurn,
+    #[msg("This account is still in its activation cooldown")]
+    AdminCooldown,
+    #[msg("Invalid cooldown")]
+    InvalidCooldown,
+    #[msg("Invalid fee")]
+    InvalidFee,
+    #[msg("Invalid treasury withdrawal")]
+    InvalidWithdrawal,
+    #[msg("These instructions are paused")]
+    InstructionsPaused,
+    #[msg("The Restaurant is paused")]
+    RestaurantPaused,
+    #[msg("Unsupported account version")]
+    UnsupportedVersion,
+    #[msg("Account is already on the latest version")]
+    AccountUpToDate,
+    #[msg("Invalid permissions")]
+    InvalidPermissions,
+    #[msg("This currency is not listed by the protocol")]
+    CurrencyNotListed,
+    #[msg("This currency does not match the restaurant currency")]
+    CurrencyMismatch,
+    #[msg("Invalid loyalty config")]
+    InvalidLoyaltyConfig,
+    #[msg("Menu items do not match the order items")]
+    InvalidMenuItems,
+    #[msg("The Restaurant still has pending orders")]
+    PendingOrders,
+    #[msg("Invalid child account")]
+    InvalidChildAccount,
+    #[msg("Invalid brand")]
+    InvalidBrand,
+    #[msg("This reward is not offered by the restaurant")]
+    InvalidReward,
+    #[msg("Invalid schedule")]
+    InvalidSchedule,
+    #[msg("The Restaurant is closed")]
+    RestaurantClosed,
+    #[msg("This menu item is not served right now")]
+    MenuItemUnavailable,
+    #[msg("Invalid table")]
+    InvalidTable,
+    #[msg("The table is not in the right status")]
+    InvalidTableStatus,
+    #[msg("The table still has open orders")]
+    TableHasOpenOrders,
+    #[msg("Invalid tax config")]
+    InvalidTaxConfig,
+    #[msg("The order payment is still escrowed")]
+    OrderEscrowed,
+    #[msg("Invalid order status")]
+    InvalidOrderStatus,
+    #[msg("The staff member is already clocked in")]
+    ShiftOpen,
+    #[msg("Invalid shift")]
+    InvalidShift,
+    #[msg("Invalid payroll accounts")]
+    InvalidPayroll,
+    #[msg("The vault doesn't hold enough settled funds")]
+    InsufficientFunds,
+    #[msg("Invalid tip pool split")]
+    InvalidTipSplit,
+    #[msg("Invalid staff role")]
+    InvalidRole,
+    #[msg("The staff member is suspended")]
+    StaffSuspended,
+    #[msg("Invalid staff status")]
+    InvalidStaffStatus,
+    #[msg("Invalid recipe")]
+    InvalidRecipe,
+    #[msg("Inventory items do not match the recipes of the order items")]
+    InvalidInventoryItems,
+    #[msg("Not enough stock for the order")]
+    InsufficientStock,
+    #[msg("Money amount overflow")]
+    MoneyOverflow,
+    #[msg("Money can't be expressed in the currency decimals")]
+    InvalidMoney,
+    #[msg("Quantity overflow")]
+    QuantityOverflow,
+    #[msg("Invalid unit of measure")]
+    InvalidUnit,
+    #[msg("Quantities have different units of measure")]
+    UnitMismatch,
+    #[msg("Not enough reward points")]
+    InsufficientPoints,
+    #[msg("Invalid reorder point or par")]
+    InvalidThresholds,
+    #[msg("The low stock index can't track more inventory items")]
+    LowStockIndexFull,
+    #[msg("Money amount underflow")]
+    MoneyUnderflow,
+    #[msg("Counter overflow")]
+    CountOverflow,
+    #[msg("Invalid vault accounts")]
+    InvalidVault,
+    #[msg("The vault still holds escrowed payments or undistributed tips")]
+    VaultNotEmpty,
+    #[msg("The restaurant still has unpaid shifts")]
+    UnpaidShifts,
+    #[msg("The tips of the closed tip period haven't reached all its staff yet")]
+    TipsPayoutPending,
 }
\ No newline at end of file
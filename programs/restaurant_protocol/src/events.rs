@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::Quantity;
+
+#[event]
+pub struct RestaurantUpdated {
+    pub restaurant: Pubkey,
+    pub reference: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub currency: Pubkey,
+    pub url: String,
+}
+
+#[event]
+pub struct RestaurantOwnerTransferred {
+    pub restaurant: Pubkey,
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct PayPeriodClosed {
+    pub restaurant: Pubkey,
+    pub member: Pubkey,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub seconds: u64,
+    pub shifts: u32,
+    pub tips: u64,
+}
+
+#[event]
+pub struct WagesPaid {
+    pub restaurant: Pubkey,
+    pub member: Pubkey,
+    pub seconds: u64,
+    pub shifts: u32,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TipsPaid {
+    pub restaurant: Pubkey,
+    pub member: Pubkey,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub seconds: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TaxPeriodClosed {
+    pub restaurant: Pubkey,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub collected: u64,
+    pub order_count: u32,
+}
+
+#[event]
+pub struct LowStock {
+    pub restaurant: Pubkey,
+    pub inventory: Pubkey,
+    pub sku: u64,
+    pub stock: Quantity,
+    pub reorder_point: Quantity,
+    pub par: Quantity,
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 mod state;
 mod errors;
+mod events;
 mod constant;
+mod auth;
 mod context;
 use context::*;
 use state::*;
@@ -12,12 +14,37 @@ declare_id!("99vazimgMSgqzg3zLBGXhZjSZVBRvThChw49VVp9U39T");
 pub mod restaurant_protocol {
     use super::*;
 
-    pub fn initialize_protocol_account(ctx: Context<ProtocolSetting>) -> Result<()> {
-        ctx.accounts.initialize_protocol()
+    pub fn initialize_protocol_account(ctx: Context<ProtocolSetting>,
+        authorities: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts.initialize_protocol(authorities, threshold)
+    }
+
+    pub fn create_proposal(ctx: Context<ProposalCreate>,
+        action: ProposalAction,
+    ) -> Result<()> {
+        ctx.accounts.propose(action)
+    }
+
+    pub fn approve_proposal(ctx: Context<ProposalApprove>) -> Result<()> {
+        ctx.accounts.approve()
+    }
+
+    pub fn execute_proposal(ctx: Context<ProposalExecute>) -> Result<()> {
+        ctx.accounts.execute()
+    }
+
+    pub fn withdraw_treasury(ctx: Context<TreasuryWithdraw>) -> Result<()> {
+        ctx.accounts.withdraw(ctx.bumps)
     }
 
-    pub fn lock_protocol(ctx: Context<ProtocolSetting>) -> Result<()> {
-        ctx.accounts.change_locked_setting()
+    pub fn list_currency(ctx: Context<CurrencyList>) -> Result<()> {
+        ctx.accounts.list()
+    }
+
+    pub fn migrate_account(ctx: Context<AccountMigrate>) -> Result<()> {
+        ctx.accounts.migrate()
     }
 
     pub fn initialize_admin_account(ctx: Context<AdminInit>, 
@@ -40,32 +67,144 @@ pub mod restaurant_protocol {
         ctx.accounts.add(reference, name, symbol, currency, url, ctx.bumps)
     }
 
-    pub fn initialize_restaurant_admin(ctx: Context<RestaurantAdminInit>, 
-        username: String
+    pub fn init_restaurant_accounts(ctx: Context<RestaurantAccountsInit>) -> Result<()> {
+        ctx.accounts.init_accounts()
+    }
+
+    pub fn close_restaurant<'info>(ctx: Context<'_, '_, '_, 'info, RestaurantClose<'info>>) -> Result<()> {
+        ctx.accounts.close(ctx.remaining_accounts, ctx.bumps)
+    }
+
+    pub fn update_restaurant(ctx: Context<RestaurantUpdate>, 
+        reference: Pubkey,
+        name: String,
+        symbol: String,
+        currency: Pubkey,
+        url: String,
     ) -> Result<()> {
-        ctx.accounts.initialize_admin(username)
+        ctx.accounts.update(reference, name, symbol, currency, url, ctx.bumps)
     }
 
-    pub fn remove_restaurant_admin(ctx: Context<RestaurantAdminRemove>) -> Result<()> {
-        ctx.accounts.remove_admin()
+    pub fn propose_restaurant_owner(ctx: Context<RestaurantOwnerPropose>, 
+        new_owner: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.propose_owner(new_owner)
     }
 
-    pub fn initialize_employee_account(ctx: Context<EmployeeInit>, 
-        username: String
+    pub fn accept_restaurant_owner(ctx: Context<RestaurantOwnerAccept>) -> Result<()> {
+        ctx.accounts.accept_owner()
+    }
+
+    pub fn update_restaurant_fee(ctx: Context<RestaurantFeeUpdate>, 
+        fee_bps: Option<u16>,
+    ) -> Result<()> {
+        ctx.accounts.update_fee(fee_bps)
+    }
+
+    pub fn pause_restaurant(ctx: Context<RestaurantPause>, 
+        paused: bool,
+    ) -> Result<()> {
+        ctx.accounts.set_paused(paused)
+    }
+
+    pub fn add_brand(ctx: Context<BrandInit>, 
+        name: String,
     ) -> Result<()> {
-        ctx.accounts.initialize_employee(username)
+        ctx.accounts.add(name)
     }
 
-    pub fn remove_employee_account(ctx: Context<EmployeeRemove>) -> Result<()> {
-        ctx.accounts.remove_employee()
+    pub fn init_brand_collection(ctx: Context<BrandCollectionInit>, 
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.init_collection(symbol, uri, ctx.bumps)
+    }
+
+    pub fn add_brand_location(ctx: Context<BrandLocationAdd>) -> Result<()> {
+        ctx.accounts.add_location()
+    }
+
+    pub fn remove_brand_location(ctx: Context<BrandLocationRemove>) -> Result<()> {
+        ctx.accounts.remove_location()
+    }
+
+    pub fn add_staff_member(ctx: Context<StaffInit>, 
+        username: String,
+        permissions: u16,
+        hourly_wage: u64,
+        tip_points: u16,
+        role: u8,
+    ) -> Result<()> {
+        ctx.accounts.add(username, permissions, hourly_wage, tip_points, role)
+    }
+
+    pub fn update_staff_member(ctx: Context<StaffUpdate>, 
+        permissions: u16,
+        hourly_wage: u64,
+        tip_points: u16,
+        role: u8,
+    ) -> Result<()> {
+        ctx.accounts.update(permissions, hourly_wage, tip_points, role)
+    }
+
+    pub fn update_staff_status(ctx: Context<StaffStatusUpdate>,
+        status: u8,
+        reason: u8,
+    ) -> Result<()> {
+        ctx.accounts.update_status(status, reason)
+    }
+
+    pub fn remove_staff_member(ctx: Context<StaffRemove>,
+        reason: u8,
+    ) -> Result<()> {
+        ctx.accounts.remove(reason)
+    }
+
+    pub fn clock_in(ctx: Context<ShiftClockIn>) -> Result<()> {
+        ctx.accounts.clock_in()
+    }
+
+    pub fn clock_out(ctx: Context<ShiftClockOut>) -> Result<()> {
+        ctx.accounts.clock_out()
+    }
+
+    pub fn correct_shift(ctx: Context<ShiftCorrect>,
+        clock_in: i64,
+        clock_out: i64,
+    ) -> Result<()> {
+        ctx.accounts.correct(clock_in, clock_out)
+    }
+
+    pub fn close_pay_period(ctx: Context<PayPeriodClose>) -> Result<()> {
+        ctx.accounts.close_period()
+    }
+
+    pub fn run_payroll<'info>(ctx: Context<'_, '_, '_, 'info, PayrollRun<'info>>,
+        shift_counts: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.run(shift_counts, ctx.remaining_accounts, ctx.bumps)
+    }
+
+    pub fn update_tip_pool(ctx: Context<TipPoolUpdate>,
+        split: u8,
+    ) -> Result<()> {
+        ctx.accounts.update(split)
+    }
+
+    pub fn close_tip_period(ctx: Context<TipPeriodClose>) -> Result<()> {
+        ctx.accounts.close_period()
+    }
+
+    pub fn distribute_tips<'info>(ctx: Context<'_, '_, '_, 'info, TipsDistribute<'info>>) -> Result<()> {
+        ctx.accounts.distribute(ctx.remaining_accounts, ctx.bumps)
     }
 
     pub fn add_inventory(ctx: Context<InventoryAdd>, 
         sku: u64,
         category: Pubkey,
         name: String,
-        price: f64,
-        stock: f64,
+        price: Money,
+        stock: Quantity,
     ) -> Result<()> {
         ctx.accounts.add(sku, category, name, price, stock)
     }
@@ -74,8 +213,8 @@ pub mod restaurant_protocol {
         sku: u64,
         category: Pubkey,
         name: String,
-        price: f64,
-        stock: f64,
+        price: Money,
+        stock: Quantity,
         last_order: u64
     ) -> Result<()> {
         ctx.accounts.update(
@@ -88,6 +227,13 @@ pub mod restaurant_protocol {
         )
     }
 
+    pub fn update_inventory_thresholds(ctx: Context<InventoryThresholdsUpdate>, 
+        reorder_point: Quantity,
+        par: Quantity,
+    ) -> Result<()> {
+        ctx.accounts.update(reorder_point, par)
+    }
+
     pub fn remove_inventory(ctx: Context<InventoryRemove>) -> Result<()> {
         ctx.accounts.remove()
     }
@@ -96,17 +242,24 @@ pub mod restaurant_protocol {
         sku: u64,
         category: Pubkey,
         name: String,
-        price: f64,
-        ingredients: Vec<String>,
+        price: Money,
+        recipe: Vec<RecipeLine>,
         active: bool,
     ) -> Result<()> {
-        ctx.accounts.add(sku, category, name, price, ingredients, active)
+        ctx.accounts.add(sku, category, name, price, recipe, active)
     }
 
     pub fn update_menu_item(ctx: Context<MenuUpdate>, 
         active: bool,
+        dayparts: u8,
+    ) -> Result<()> {
+        ctx.accounts.update(active, dayparts)
+    }
+
+    pub fn update_menu_recipe(ctx: Context<MenuRecipeUpdate>, 
+        recipe: Vec<RecipeLine>,
     ) -> Result<()> {
-        ctx.accounts.update(active)
+        ctx.accounts.update(recipe)
     }
 
     pub fn remove_menu_item(ctx: Context<MenuRemove>) -> Result<()> {
@@ -142,6 +295,61 @@ pub mod restaurant_protocol {
         ctx.accounts.airdrop(ctx.bumps)
     }
 
+    pub fn update_loyalty_config(ctx: Context<LoyaltyUpdate>,
+        points_per_unit: u64,
+        min_spend: u64,
+        category_multipliers: Vec<CategoryMultiplier>,
+        promotions: Vec<Promotion>,
+    ) -> Result<()> {
+        ctx.accounts.update(points_per_unit, min_spend, category_multipliers, promotions)
+    }
+
+    pub fn update_schedule(ctx: Context<ScheduleUpdate>,
+        utc_offset: i32,
+        windows: Vec<OpeningWindow>,
+        closed_dates: Vec<i64>,
+        dayparts: Vec<Daypart>,
+    ) -> Result<()> {
+        ctx.accounts.update(utc_offset, windows, closed_dates, dayparts)
+    }
+
+    pub fn update_tax_config(ctx: Context<TaxConfigUpdate>,
+        default_rate_bps: u16,
+        category_rates: Vec<CategoryTaxRate>,
+    ) -> Result<()> {
+        ctx.accounts.update(default_rate_bps, category_rates)
+    }
+
+    pub fn close_tax_period(ctx: Context<TaxPeriodClose>) -> Result<()> {
+        ctx.accounts.close_period()
+    }
+
+    pub fn add_table(ctx: Context<TableInit>,
+        table_id: u16,
+        label: String,
+        capacity: u8,
+    ) -> Result<()> {
+        ctx.accounts.add(table_id, label, capacity)
+    }
+
+    pub fn remove_table(ctx: Context<TableRemove>) -> Result<()> {
+        ctx.accounts.remove()
+    }
+
+    pub fn seat_table(ctx: Context<TableSeat>,
+        party_size: u8,
+    ) -> Result<()> {
+        ctx.accounts.seat(party_size)
+    }
+
+    pub fn transfer_table<'info>(ctx: Context<'_, '_, '_, 'info, TableTransfer<'info>>) -> Result<()> {
+        ctx.accounts.transfer(ctx.remaining_accounts)
+    }
+
+    pub fn clear_table(ctx: Context<TableClear>) -> Result<()> {
+        ctx.accounts.clear()
+    }
+
     pub fn add_customer(ctx: Context<CustomerInit>,
         id: u64,
         uri: String,
@@ -150,26 +358,31 @@ pub mod restaurant_protocol {
         ctx.accounts.add(id, uri, attributes, ctx.bumps)
     }
 
-    pub fn add_order(ctx: Context<OrderInit>, 
+    pub fn add_order<'info>(ctx: Context<'_, '_, '_, 'info, OrderInit<'info>>, 
         order_id: u64,
-        total: f32,
         items: Vec<u64>,
-        
+        tip: u64,
     ) -> Result<()> {
-        ctx.accounts.add(order_id, total, items)
+        ctx.accounts.add(order_id, items, tip, ctx.remaining_accounts)
     }
 
     pub fn update_order(ctx: Context<OrderUpdate>, 
         status: u8,
     ) -> Result<()> {
-        ctx.accounts.update(status)
+        ctx.accounts.update(status, ctx.bumps)
     }
 
-    pub fn cancel_order(ctx: Context<OrderCancel>) -> Result<()> {
-        ctx.accounts.cancel()
+    pub fn cancel_order<'info>(ctx: Context<'_, '_, '_, 'info, OrderCancel<'info>>) -> Result<()> {
+        ctx.accounts.cancel(ctx.remaining_accounts, ctx.bumps)
     }
 
     pub fn close_order(ctx: Context<OrderClose>) -> Result<()> {
         ctx.accounts.close()
     }
+
+    pub fn withdraw_vault(ctx: Context<VaultWithdraw>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw(amount, ctx.bumps)
+    }
 }
\ No newline at end of file
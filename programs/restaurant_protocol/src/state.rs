@@ -1,28 +1,181 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
+use crate::{
+    constant::{
+        MAX_AUTHORITIES,
+        DEFAULT_POINTS_PER_UNIT,
+        MAX_CATEGORY_MULTIPLIERS,
+        MAX_PROMOTIONS,
+        MAX_OPENING_WINDOWS,
+        MAX_CLOSED_DATES,
+        MAX_DAYPARTS,
+        MAX_TABLE_LABEL_LEN,
+        MAX_TABLE_ORDERS,
+        MAX_TAX_RATES,
+        MAX_RECIPE_LINES,
+        MAX_LOW_STOCK_ITEMS,
+        UNIT_EACH,
+        UNIT_GRAM,
+        UNIT_MILLILITRE,
+        MAX_FEE_BPS,
+        ORDER_PENDING,
+        ORDER_COMPLETED,
+        ORDER_FINALIZED,
+        ORDER_READY,
+        ROLE_NONE,
+        ROLE_CASHIER,
+        ROLE_KITCHEN,
+        ROLE_EXPO,
+        STAFF_ACTIVE,
+        DEFAULT_ADMIN_COOLDOWN,
+        multisig_wallet,
+    },
+    errors::{SetupError, ProtocolError},
+};
+
+// Every account stores its layout version right after the discriminator. When a struct changes, bump its
+// VERSION and teach `upgrade` to decode the previous layout so `migrate_account` can move live accounts over.
+// Accounts created before versioning have no version byte, `is_unversioned` tells them apart by their size and
+// they are upgraded from version 0.
+pub trait Versioned: AccountSerialize + AccountDeserialize + AnchorSerialize + Discriminator {
+    const VERSION: u8;
+
+    fn is_unversioned(_data: &[u8]) -> bool {
+        false
+    }
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!(version == Self::VERSION, ProtocolError::UnsupportedVersion);
+        Self::try_deserialize(&mut &data[..])
+    }
+}
 
 // Setup State
 #[account]
 pub struct Protocol {
+    pub version: u8,              // Layout version of the account -- see Versioned
     pub locked: bool,
+    pub authorities: Vec<Pubkey>, // Governance authority set -- signers allowed to propose/approve/execute sensitive actions
+    pub threshold: u8,            // Number of authority approvals required before a proposal can be executed
+    pub proposal_count: u64,      // Number of proposals created so far -- used as the seed for the next proposal
+    pub admin_cooldown: i64,      // Seconds a new Admin / StaffMember has to wait before it can act
+    pub fee_bps: u16,             // Protocol fee taken on order payments, in basis points
+    pub paused: u8,               // Bitmask of paused instruction families, see the PAUSE_* constants
 }
 
 impl Space for Protocol {
-    const INIT_SPACE: usize = 8 + 1;
+    const INIT_SPACE: usize = 8 + 1 + 1 + 4 + 32 * MAX_AUTHORITIES + 1 + 8 + 8 + 2 + 1;
+}
+
+impl Protocol {
+    pub fn is_authority(&self, key: &Pubkey) -> bool {
+        self.authorities.contains(key)
+    }
+
+    pub fn validate_authorities(authorities: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(!authorities.is_empty() && authorities.len() <= MAX_AUTHORITIES, SetupError::InvalidAuthorities);
+        require!(
+            authorities.iter().enumerate().all(|(i, key)| !authorities[..i].contains(key)),
+            SetupError::InvalidAuthorities
+        );
+        require!(threshold > 0 && threshold as usize <= authorities.len(), SetupError::InvalidThreshold);
+
+        Ok(())
+    }
+
+    pub fn set_authorities(&mut self, authorities: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        Protocol::validate_authorities(&authorities, threshold)?;
+
+        self.authorities = authorities;
+        self.threshold = threshold;
+
+        Ok(())
+    }
+
+    pub fn require_unpaused(&self, flag: u8) -> Result<()> {
+        require!(self.paused & flag == 0, ProtocolError::InstructionsPaused);
+
+        Ok(())
+    }
+
+    // Admin and StaffMember records store their `initialized` timestamp, they can't act until the cooldown is over.
+    pub fn require_active(&self, initialized: i64) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= initialized.saturating_add(self.admin_cooldown),
+            ProtocolError::AdminCooldown
+        );
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Proposal {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub id: u64,                  // Proposal ID -- index of the proposal in the protocol
+    pub proposer: Pubkey,         // Authority that created the proposal
+    pub action: ProposalAction,   // Action that will be performed once the proposal is executed
+    pub approvals: Vec<Pubkey>,   // Authorities that approved the proposal, the proposer approves on creation
+    pub executed: bool,           // Whether the proposal has already been executed
+    pub created_at: i64,          // Created at -- stored as unix timestamp
+}
+
+impl Space for Proposal {
+    const INIT_SPACE: usize = 8 + 1 + 8 + 32 + ProposalAction::MAX_SPACE + 4 + 32 * MAX_AUTHORITIES + 1 + 8;
+}
+
+impl Proposal {
+    // Only approvals from the current authority set count, so rotating out a key also drops its pending approvals.
+    pub fn is_approved(&self, protocol: &Protocol) -> bool {
+        let approvals = self.approvals.iter().filter(|key| protocol.is_authority(key)).count();
+        approvals >= protocol.threshold as usize
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, PartialEq)]
+pub enum ProposalAction {
+    ToggleLock,
+    AddAdmin { admin: Pubkey },
+    RemoveAdmin { admin: Pubkey },
+    SetAuthorities { authorities: Vec<Pubkey>, threshold: u8 },
+    SetAdminCooldown { cooldown: i64 },
+    SetProtocolFee { fee_bps: u16 },
+    SetPauseFlags { flags: u8 },
+    WithdrawTreasury { mint: Pubkey, amount: u64, destination: Pubkey },
+    ListCurrency { mint: Pubkey, listed: bool },
+}
+
+impl ProposalAction {
+    // Largest variant is SetAuthorities: discriminator + vec prefix + keys + threshold
+    pub const MAX_SPACE: usize = 1 + 4 + 32 * MAX_AUTHORITIES + 1;
+}
+
+#[account]
+pub struct CurrencyListing {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub mint: Pubkey,             // Payment mint (USDC, PYUSD, ...)
+    pub decimals: u8,             // Decimals of the mint
+    pub listed: bool,             // Whether restaurants can use the mint, delisting keeps the account for history
+}
+
+impl Space for CurrencyListing {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 1 + 1;
 }
 
 #[account]
 pub struct Admin {
+    pub version: u8,              // Layout version of the account -- see Versioned
     pub publickey: Pubkey,
     pub username: String,
     pub initialized: i64,
 }
 
 impl Space for Admin {
-    const INIT_SPACE: usize = 8 + 32 + 4 + 8;
+    const INIT_SPACE: usize = 8 + 1 + 32 + 4 + 8;
 }
 
 #[account]
 pub struct Restaurant {
+    pub version: u8,              // Layout version of the account -- see Versioned
     pub reference: Pubkey, // how we will sort
     pub name: String,
     pub symbol: String,
@@ -30,105 +183,355 @@ pub struct Restaurant {
     pub currency: Pubkey,
     pub url: String,
     pub customer_count: u32,
+    pub protocol_fee_bps: Option<u16>, // overrides Protocol.fee_bps for this restaurant when set
+    pub paused: bool,                  // freezes this restaurant only, set by the owner or a protocol admin
+    pub pending_orders: u32,           // orders still open (pending or ready), the restaurant can't be closed while any are left
+    pub seed: Pubkey,                  // founding owner, the PDA stays derived from it after ownership transfers
+    pub pending_owner: Option<Pubkey>, // proposed new owner, has to accept the transfer
+    pub brand: Option<Pubkey>,         // Brand of the location -- memberships, points and brand catalog are shared
 }
 
 impl Space for Restaurant {
-    const INIT_SPACE: usize = 8 + 32 + 4 + 32 + 4 + 32 + 4 + 32 + 32+ 4 + 4;
+    const INIT_SPACE: usize = 8 + 1 + 32 + 4 + 32 + 4 + 32 + 4 + 32 + 32+ 4 + 4 + 1 + 2 + 1 + 4 + 32 + 1 + 32 + 1 + 32;
+}
+
+impl Restaurant {
+    pub fn fee_bps(&self, protocol: &Protocol) -> u16 {
+        // the override is a discount, a later governance cut of the default still applies
+        self.protocol_fee_bps.map_or(protocol.fee_bps, |fee_bps| fee_bps.min(protocol.fee_bps))
+    }
+
+    // Menu items and rewards belong either to this restaurant or to its brand
+    pub fn offers(&self, key: &Pubkey, owner: &Pubkey) -> bool {
+        owner == key || self.brand.as_ref() == Some(owner)
+    }
 }
 
 #[account]
-pub struct RestaurantAdmin {
-    pub publickey: Pubkey,
-    pub restaurant: Pubkey,
+pub struct Brand {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub owner: Pubkey,            // Brand owner -- manages the brand catalog and its locations
+    pub name: String,
+    pub location_count: u32,      // Number of restaurants in the brand
+    pub customer_count: u32,      // Memberships issued by the brand, they join the brand collection mint
+}
+
+impl Space for Brand {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 4 + 4 + 4;
+}
+
+#[account]
+pub struct StaffMember {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub publickey: Pubkey,        // Wallet of the staff member
+    pub restaurant: Pubkey,       // Restaurant the staff member works for
     pub username: String,
-    pub initialized: i64,
+    pub permissions: u16,         // Bitmask of the PERMISSION_* constants
+    pub initialized: i64,         // Created at -- the staff member can't act before Protocol.admin_cooldown has passed
+    pub hourly_wage: u64,         // Wage per hour worked, in restaurant currency base units
+    pub tip_points: u16,          // Weight of the staff member in a TIP_SPLIT_POINTS tip pool
+    pub role: u8,                 // ROLE_* of the staff member, limits the order transitions it can make
+    pub status: u8,               // STAFF_* status -- suspended staff can't act
+    pub status_changed_at: i64,   // When the status last changed, stored as unix timestamp
+    pub status_reason: u8,        // Reason code given for the last status change, defined by the restaurant
+}
+
+impl Space for StaffMember {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 4 + 2 + 8 + 8 + 2 + 1 + 1 + 8 + 1;
+}
+
+#[account]
+pub struct StaffTombstone {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the staff member worked for
+    pub member: Pubkey,           // Wallet of the former staff member
+    pub hired_at: i64,            // When the StaffMember record was created, stored as unix timestamp
+    pub terminated_at: i64,       // When the staff member was terminated, stored as unix timestamp
+    pub reason: u8,               // Reason code of the termination, defined by the restaurant
+    pub terminations: u16,        // Times the wallet was terminated at this restaurant -- the tombstone keeps the last one
+    pub hourly_wage: u64,         // Wage at termination -- pays the shifts worked before it, see PayrollRun
+    pub tip_points: u16,          // Tip points at termination
 }
 
-impl Space for RestaurantAdmin {
-    const INIT_SPACE: usize = 8 + 32 + 32 + 4 + 8;
+impl Space for StaffTombstone {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 1 + 2 + 8 + 2;
 }
 
+impl StaffMember {
+    pub fn has_permission(&self, permission: u16) -> bool {
+        self.permissions & permission == permission
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == STAFF_ACTIVE
+    }
+
+    // Kitchen fires orders, expo hands them off and the cashier also settles them. Managers and staff
+    // without a role can make any transition their permissions allow.
+    pub fn can_move_order(&self, from: u8, to: u8) -> bool {
+        match self.role {
+            ROLE_KITCHEN => matches!((from, to), (ORDER_PENDING, ORDER_READY) | (ORDER_READY, ORDER_PENDING)),
+            ROLE_EXPO => (from, to) == (ORDER_READY, ORDER_COMPLETED),
+            ROLE_CASHIER => matches!((from, to), (ORDER_READY, ORDER_COMPLETED) | (ORDER_COMPLETED, ORDER_FINALIZED)),
+            _ => true,
+        }
+    }
+
+    // Kitchen and expo never touch the money, they can't refund (cancel) or close orders.
+    pub fn handles_payments(&self) -> bool {
+        !matches!(self.role, ROLE_KITCHEN | ROLE_EXPO)
+    }
+}
 
 #[account]
-pub struct Employee {
-    pub publickey: Pubkey,
-    pub restaurant: Pubkey,
-    pub username: String,
-    pub initialized: i64,
+pub struct Shift {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the shift was worked at
+    pub member: Pubkey,           // Wallet of the staff member who worked the shift
+    pub shift_id: u64,            // Index of the shift in the Timesheet -- seed of the shift
+    pub clock_in: i64,            // Clocked in at, stored as unix timestamp
+    pub clock_out: i64,           // Clocked out at, stored as unix timestamp -- 0 while the shift is open
+    pub corrected_by: Option<Pubkey>, // Manager who last corrected the shift
+    pub paid: bool,               // Paid by a payroll run -- paid shifts can't be paid or corrected again
+}
+
+impl Space for Shift {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8 + 33 + 1;
+}
+
+impl Shift {
+    pub fn seconds(&self) -> u64 {
+        (self.clock_out - self.clock_in).max(0) as u64
+    }
+}
+
+#[account]
+pub struct Timesheet {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the staff member works at
+    pub member: Pubkey,           // Wallet of the staff member
+    pub shift_count: u64,         // Shifts clocked so far -- seed of the next shift
+    pub open_shift: Option<Pubkey>, // Shift the staff member is clocked in to, only one can be open
+    pub period_start: i64,        // When the current pay period started, stored as unix timestamp
+    pub period_seconds: u64,      // Seconds worked in finalized shifts of the current pay period
+    pub period_shifts: u32,       // Shifts finalized in the current pay period
+    pub last_period_start: i64,   // Start of the last closed pay period -- 0 until a period is closed
+    pub last_period_end: i64,     // End of the last closed pay period
+    pub last_period_seconds: u64, // Seconds worked in the last closed pay period
+    pub last_period_shifts: u32,  // Shifts finalized in the last closed pay period
+    pub period_tips: u64,         // Tips paid out in the current pay period, in currency base units
+    pub last_period_tips: u64,    // Tips paid out in the last closed pay period, in currency base units
+    pub tip_period: i64,          // TipPool.period_start of the tip period the tip time below was clocked out in -- 0 if none
+    pub tip_seconds: u64,         // Seconds of the shifts clocked out in that tip period
+    pub tip_points: u16,          // StaffMember.tip_points at the last clock in, weighs the shift when it is clocked out
+    pub tip_weight: u64,          // Tip seconds times the tip points of their shifts
+    pub unpaid_tip_period: i64,   // Closed tip period whose payout hasn't reached the staff member yet -- 0 if none
+    pub unpaid_tip_seconds: u64,  // Tip seconds of that closed tip period
+    pub unpaid_tip_weight: u64,   // Tip weight of that closed tip period
 }
 
-impl Space for Employee {
-    const INIT_SPACE: usize = 8 + 32 + 4 + 8;
+impl Space for Timesheet {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 8 + 33 + 8 + 8 + 4 + 8 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 2 + 8 + 8 + 8 + 8;
 }
 
 #[account]
 pub struct InventoryItem {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,    // Restaurant of the product -- lets the restaurant closure verify its children
     pub sku: u64,              // Stock Keeping Unit -- how we identify the product
     pub category: Pubkey,      // Category of the product -- stored as public key for easy sorting and filtering
     pub name: String,          // Name of the product -- what the product is called
-    pub price: f64,            // Price of the product -- how much it costs for ordering
-    pub stock: f64,            // Stock of the product -- how many units are available, will be updated as orders are made
+    pub price: Money,          // Price of the product -- how much it costs for ordering
+    pub stock: Quantity,       // Stock of the product -- how much is available, deducted by the recipes of ordered menu items
     pub last_order: u64,       // Last time the product was ordered -- stored as unix timestamp
+    pub reorder_point: Quantity, // Reorder point of the product -- LowStock is emitted once the stock drops below it
+    pub par: Quantity,         // Par level of the product -- listed in the LowStockIndex while the stock is below it, 0 is untracked
 }
 
+// without the bytes of `name`
 impl Space for InventoryItem {
-    const INIT_SPACE: usize = 8 + 32 + 4 + 8 + 8 + 8;
+    const INIT_SPACE: usize = 8 + 1 + 32 + 8 + 32 + 4 + Money::SPACE + Quantity::SPACE + 8 + Quantity::SPACE * 2;
+}
+
+impl InventoryItem {
+    pub fn is_tracked(&self) -> bool {
+        self.par.amount > 0
+    }
+
+    pub fn is_below_par(&self) -> bool {
+        self.stock.amount < self.par.amount
+    }
+
+    pub fn is_low(&self) -> bool {
+        self.stock.amount < self.reorder_point.amount
+    }
 }
 
 #[account]
 pub struct MenuItem {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,    // Restaurant of the product -- lets orders verify the menu items they are passed
     pub sku: u64,              // Stock Keeping Unit -- how we identify the product
     pub category: Pubkey,      // Category of the product -- stored as public key for easy sorting and filtering
     pub name: String,          // Name of the product -- what the product is called
-    pub price: f64,            // Price of the product -- how much it costs for ordering
-    pub ingredients: Vec<String>, // Ingredients of the product -- what is used to make the product, this is what will be used to deduct from the inventory
+    pub price: Money,          // Price of the product -- how much it costs for ordering
+    pub recipe: Vec<RecipeLine>, // Recipe of the product -- the inventory used to make one unit, deducted from the stock when ordered
     pub active: bool,          // Active status of the product -- whether it is available for ordering
+    pub dayparts: u8,          // Dayparts of the product -- bitmask of Schedule.dayparts it is served in, 0 is all day
 }
 
+// without the bytes of `name` and the `recipe` lines, see RecipeLine::SPACE
 impl Space for MenuItem {
-    const INIT_SPACE: usize = 8 + 32 + 4 + 8 + 8 + 8 + 8;
+    const INIT_SPACE: usize = 8 + 1 + 32 + 8 + 32 + 4 + Money::SPACE + 4 + 1 + 1;
+}
+
+impl MenuItem {
+    pub fn validate_recipe(recipe: &[RecipeLine]) -> Result<()> {
+        require!(recipe.len() <= MAX_RECIPE_LINES, ProtocolError::InvalidRecipe);
+
+        for (i, line) in recipe.iter().enumerate() {
+            line.quantity.validate()?;
+            require!(line.quantity.amount > 0, ProtocolError::InvalidRecipe);
+            require!(
+                !recipe[..i].iter().any(|other| other.inventory == line.inventory),
+                ProtocolError::InvalidRecipe
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct Customer {
+    pub version: u8,              // Layout version of the account -- see Versioned
     pub id: u64,
-    pub restaurant: Pubkey,
+    pub issuer: Pubkey,           // Brand of the location the customer signed up at, or the standalone restaurant
     pub publickey: Pubkey,
     pub customer_nft: Pubkey,
     pub member_since: i64,
 }
 
-impl Space for Customer {
-    const INIT_SPACE: usize = 8 + 8 + 8;
-}
-
 #[account]
+#[derive(InitSpace)]
 pub struct CustomerNft {
+    pub version: u8,              // Layout version of the account -- see Versioned
     pub id: u64,
     pub reward_points: u64,
 }
 
-impl Space for CustomerNft {
-    const INIT_SPACE: usize = 8 + 8 + 32 + 4 + 2 + 32 + 2 + 8;
-}
-
 #[account]
 pub struct CustomerOrder {
+    pub version: u8,              // Layout version of the account -- see Versioned
     pub order_id: u64,         // Order ID -- unique identifier for the order
+    pub restaurant: Pubkey,    // Restaurant of the order -- lets the restaurant closure verify its children
     pub customer: Pubkey,      // Customer of the order -- who made the order
     pub items: Vec<u64>,       // Items in the order -- what products were ordered, skus of the products
-    pub total: f32,            // Total of the order -- how much the order costs
-    pub status: u8,            // Status of the order -- what state the order is in (0: pending, 1: completed, 2: finalized, 3: cancelled)
+    pub total: Money,          // Total of the order -- how much the order costs, tax included
+    pub status: u8,            // Status of the order -- what state the order is in, see the ORDER_* constants
     pub created_at: i64,       // Created at -- when the order was made, stored as unix timestamp
     pub updated_at: i64,       // Updated at -- when the order was last updated, stored as unix timestamp
+    pub table: Option<Pubkey>, // Table of the order -- dine-in orders are listed on the table until they are closed
+    pub subtotal: Money,       // Subtotal of the order -- sum of the menu item prices
+    pub tax: Money,            // Tax of the order -- computed from the restaurant TaxConfig
+    pub escrow: u64,           // Payment held in the restaurant Vault, in currency base units -- 0 once released or refunded
+    pub tip: u64,              // Tip of the order, in currency base units -- part of the escrow, goes to the TipPool on release
+    pub points: u64,           // Reward points of the order -- credited to the customer on release, never for cancelled orders
+    pub stock: Vec<RecipeLine>, // Inventory deducted by the recipes when the order was placed -- put back if it is cancelled
+}
+
+impl CustomerOrder {
+    // Open orders count towards Restaurant.pending_orders and keep their payment escrowed.
+    pub fn is_open(&self) -> bool {
+        Self::is_open_status(self.status)
+    }
+
+    pub fn is_open_status(status: u8) -> bool {
+        status == ORDER_PENDING || status == ORDER_READY
+    }
 }
 
+// without the 8 bytes of every sku in `items` and the lines of `stock`
 impl Space for CustomerOrder {
-    const INIT_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1 + 8 + 8;
+    const INIT_SPACE: usize = 8 + 1 + 8 + 32 + 32 + 4 + Money::SPACE + 1 + 8 + 8 + 33 + Money::SPACE * 2 + 8 + 8 + 8 + 4;
+}
+
+#[account]
+pub struct Vault {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the funds belong to
+    pub currency: Pubkey,         // Mint of the vault token account
+    pub escrowed: u64,            // Payments of pending orders, in currency base units
+    pub settled: u64,             // Payments released to the restaurant the owner can withdraw, in currency base units
+}
+
+impl Space for Vault {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 8 + 8;
+}
+
+#[account]
+pub struct TipPool {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the tips were left at
+    pub vault: Pubkey,            // Vault holding the tips, they are kept apart from its escrowed and settled funds
+    pub split: u8,                // How tips are split (0: by hours worked, 1: by hours worked times StaffMember.tip_points)
+    pub balance: u64,             // Tips waiting to be distributed, in currency base units
+    pub period_start: i64,        // Start of the current tip period -- staff clocked in since then share the balance
+    pub distributed: u64,         // Tips distributed so far, in currency base units
+    pub period_members: u32,      // Staff members who clocked out a shift during the current tip period -- see Timesheet.tip_period
+    pub period_seconds: u64,      // Seconds of the shifts clocked out during the current tip period
+    pub period_weight: u64,       // Those seconds times the tip points of their shifts
+    pub payout_start: i64,        // Start of the closed tip period being paid out
+    pub payout_end: i64,          // End of the closed tip period being paid out
+    pub payout_amount: u64,       // Tips of the closed tip period, taken out of the balance when it was closed
+    pub payout_weight: u64,       // Total weight the payout is split by, seconds or seconds times tip points
+    pub payout_by_points: bool,   // Whether the payout is split by tip points, a TIP_SPLIT_POINTS pool without points splits by hours
+    pub payout_members: u32,      // Staff members of the closed tip period still waiting for their payout
+    pub payout_paid: u64,         // Tips paid out of payout_amount so far
+}
+
+impl Space for TipPool {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 1 + 8 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 4 + 8;
+}
+
+impl TipPool {
+    // The tips of a closed tip period have to reach all its staff before the next period can be closed.
+    pub fn is_paying_out(&self) -> bool {
+        self.payout_members > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.balance == 0 && !self.is_paying_out()
+    }
+}
+
+#[account]
+pub struct Table {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant of the table
+    pub table_id: u16,            // Table number -- seed of the table
+    pub label: String,            // Label shown on the floor plan
+    pub capacity: u8,             // Seats at the table
+    pub status: u8,               // Status of the table (0: free, 1: seated, 2: dirty)
+    pub party_size: u8,           // Guests seated at the table
+    pub seated_at: i64,           // When the current party was seated, stored as unix timestamp
+    pub open_orders: Vec<Pubkey>, // Order accounts bound to the table that are not closed yet
+}
+
+impl Space for Table {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 2 + 4 + MAX_TABLE_LABEL_LEN + 1 + 1 + 1 + 8 + 4 + 32 * MAX_TABLE_ORDERS;
+}
+
+impl Table {
+    pub fn remove_order(&mut self, order: &Pubkey) {
+        self.open_orders.retain(|open_order| open_order != order);
+    }
 }
 
 #[account]
 pub struct Reward {
+    pub version: u8,              // Layout version of the account -- see Versioned
     pub category: Pubkey,
     pub restaurant: Pubkey,
     pub reward_points: u64,
@@ -136,11 +539,1394 @@ pub struct Reward {
 }
 
 impl Space for Reward {
-    const INIT_SPACE: usize = 8 + 32 + 4 + 32 + 4 + 8 + 32 + 4;
+    const INIT_SPACE: usize = 8 + 1 + 32 + 4 + 32 + 4 + 8 + 32 + 4;
+}
+
+#[account]
+pub struct LoyaltyConfig {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the rules apply to
+    pub points_per_unit: u64,     // Points earned per whole unit of the restaurant currency
+    pub min_spend: u64,           // Minimum order subtotal (before tax) in currency base units -- smaller orders earn no points
+    pub category_multipliers: Vec<CategoryMultiplier>, // Multipliers keyed by MenuItem.category
+    pub promotions: Vec<Promotion>, // Time-boxed multipliers -- the highest active one applies
+}
+
+impl Space for LoyaltyConfig {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 8 + 8 + 4 + 34 * MAX_CATEGORY_MULTIPLIERS + 4 + 20 * MAX_PROMOTIONS;
+}
+
+impl LoyaltyConfig {
+    // default earning rules of a new restaurant
+    pub fn new(restaurant: Pubkey) -> Self {
+        LoyaltyConfig {
+            version: Self::VERSION,
+            restaurant,
+            points_per_unit: DEFAULT_POINTS_PER_UNIT,
+            min_spend: 0,
+            category_multipliers: Vec::new(),
+            promotions: Vec::new(),
+        }
+    }
+
+    pub fn validate(category_multipliers: &[CategoryMultiplier], promotions: &[Promotion]) -> Result<()> {
+        require!(category_multipliers.len() <= MAX_CATEGORY_MULTIPLIERS, ProtocolError::InvalidLoyaltyConfig);
+        require!(promotions.len() <= MAX_PROMOTIONS, ProtocolError::InvalidLoyaltyConfig);
+
+        for (i, multiplier) in category_multipliers.iter().enumerate() {
+            require!(
+                !category_multipliers[..i].iter().any(|other| other.category == multiplier.category),
+                ProtocolError::InvalidLoyaltyConfig
+            );
+        }
+
+        for promotion in promotions {
+            require!(promotion.starts_at < promotion.ends_at, ProtocolError::InvalidLoyaltyConfig);
+            require!(promotion.weekday.unwrap_or(0) < 7, ProtocolError::InvalidLoyaltyConfig);
+        }
+
+        Ok(())
+    }
+
+    pub fn category_multiplier(&self, category: &Pubkey) -> u16 {
+        self.category_multipliers
+            .iter()
+            .find(|multiplier| multiplier.category == *category)
+            .map(|multiplier| multiplier.multiplier_bps)
+            .unwrap_or(10_000)
+    }
+
+    pub fn promotion_multiplier(&self, now: i64) -> u16 {
+        // 1970-01-01 was a Thursday, weekdays are counted from Sunday = 0
+        let weekday = ((now.div_euclid(86_400) + 4) % 7) as u8;
+
+        self.promotions
+            .iter()
+            .filter(|promotion| promotion.starts_at <= now && now < promotion.ends_at)
+            .filter(|promotion| promotion.weekday.map(|day| day == weekday).unwrap_or(true))
+            .map(|promotion| promotion.multiplier_bps)
+            .max()
+            .unwrap_or(10_000)
+    }
+
+    // points and min_spend both use the subtotal before tax, `lines` are the (category, price) of every
+    // ordered menu item and only weight the multiplier
+    pub fn points(&self, subtotal: Money, lines: &[(Pubkey, Money)], now: i64) -> Result<u64> {
+        if subtotal.amount < self.min_spend {
+            return Ok(0);
+        }
+
+        let mut list_price: u128 = 0;
+        let mut weighted: u128 = 0;
+        for (category, price) in lines {
+            list_price += price.amount as u128;
+            weighted += price.amount as u128 * self.category_multiplier(category) as u128;
+        }
+        let category_multiplier = weighted.checked_div(list_price).unwrap_or(10_000);
+
+        let unit = 10u128.checked_pow(subtotal.decimals as u32).ok_or(ProtocolError::MoneyOverflow)?;
+        let points = (subtotal.amount as u128)
+            .checked_mul(self.points_per_unit as u128)
+            .and_then(|points| points.checked_mul(category_multiplier))
+            .and_then(|points| points.checked_mul(self.promotion_multiplier(now) as u128))
+            .ok_or(ProtocolError::InvalidLoyaltyConfig)?
+            / unit / 10_000 / 10_000;
+
+        u64::try_from(points).map_err(|_| ProtocolError::InvalidLoyaltyConfig.into())
+    }
+}
+
+#[account]
+pub struct TaxConfig {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the rates apply to
+    pub default_rate_bps: u16,    // Rate applied to categories without their own rate, in basis points
+    pub category_rates: Vec<CategoryTaxRate>, // Rates keyed by MenuItem.category
+}
+
+impl Space for TaxConfig {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 2 + 4 + 34 * MAX_TAX_RATES;
+}
+
+impl TaxConfig {
+    // a new restaurant is untaxed
+    pub fn new(restaurant: Pubkey) -> Self {
+        TaxConfig {
+            version: Self::VERSION,
+            restaurant,
+            default_rate_bps: 0,
+            category_rates: Vec::new(),
+        }
+    }
+
+    pub fn validate(default_rate_bps: u16, category_rates: &[CategoryTaxRate]) -> Result<()> {
+        require!(default_rate_bps <= MAX_FEE_BPS, ProtocolError::InvalidTaxConfig);
+        require!(category_rates.len() <= MAX_TAX_RATES, ProtocolError::InvalidTaxConfig);
+
+        for (i, rate) in category_rates.iter().enumerate() {
+            require!(rate.rate_bps <= MAX_FEE_BPS, ProtocolError::InvalidTaxConfig);
+            require!(
+                !category_rates[..i].iter().any(|other| other.category == rate.category),
+                ProtocolError::InvalidTaxConfig
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn rate(&self, category: &Pubkey) -> u16 {
+        self.category_rates
+            .iter()
+            .find(|rate| rate.category == *category)
+            .map(|rate| rate.rate_bps)
+            .unwrap_or(self.default_rate_bps)
+    }
+
+    pub fn tax(&self, category: &Pubkey, price: Money) -> Result<Money> {
+        price.bps(self.rate(category))
+    }
+}
+
+#[account]
+pub struct TaxLedger {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the tax was collected for
+    pub period_start: i64,        // When the current reporting period started, stored as unix timestamp
+    pub collected: u64,           // Tax collected in the current period, in currency base units
+    pub order_count: u32,         // Orders placed in the current period
+    pub last_period_start: i64,   // Start of the last closed period -- 0 until a period is closed
+    pub last_period_end: i64,     // End of the last closed period
+    pub last_period_collected: u64, // Tax collected in the last closed period, in currency base units
+    pub last_period_order_count: u32, // Orders placed in the last closed period
+}
+
+impl Space for TaxLedger {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 4;
+}
+
+impl TaxLedger {
+    pub fn new(restaurant: Pubkey, now: i64) -> Self {
+        TaxLedger {
+            version: Self::VERSION,
+            restaurant,
+            period_start: now,
+            collected: 0,
+            order_count: 0,
+            last_period_start: 0,
+            last_period_end: 0,
+            last_period_collected: 0,
+            last_period_order_count: 0,
+        }
+    }
+}
+
+#[account]
+pub struct LowStockIndex {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the inventory belongs to
+    pub tracked: u16,             // Inventory items with a par level, at most MAX_LOW_STOCK_ITEMS so every one of them fits in `items`
+    pub items: Vec<Pubkey>,       // InventoryItem PDAs whose stock is below par
+}
+
+impl Space for LowStockIndex {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 2 + 4 + 32 * MAX_LOW_STOCK_ITEMS;
+}
+
+impl LowStockIndex {
+    pub fn new(restaurant: Pubkey) -> Self {
+        LowStockIndex {
+            version: Self::VERSION,
+            restaurant,
+            tracked: 0,
+            items: Vec::new(),
+        }
+    }
+
+    // lists the item while its stock is below par
+    pub fn refresh(&mut self, inventory: Pubkey, item: &InventoryItem) -> Result<()> {
+        match (self.items.iter().position(|key| *key == inventory), item.is_below_par()) {
+            (None, true) => {
+                require!(self.items.len() < MAX_LOW_STOCK_ITEMS, ProtocolError::LowStockIndexFull);
+                self.items.push(inventory);
+            },
+            (Some(index), false) => {
+                self.items.swap_remove(index);
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+
+    pub fn track(&mut self, was_tracked: bool, tracked: bool) -> Result<()> {
+        match (was_tracked, tracked) {
+            (false, true) => {
+                require!((self.tracked as usize) < MAX_LOW_STOCK_ITEMS, ProtocolError::LowStockIndexFull);
+                self.tracked += 1;
+            },
+            (true, false) => self.tracked -= 1,
+            _ => {},
+        }
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Schedule {
+    pub version: u8,              // Layout version of the account -- see Versioned
+    pub restaurant: Pubkey,       // Restaurant the schedule applies to
+    pub utc_offset: i32,          // Seconds added to UTC to get the restaurant local time
+    pub windows: Vec<OpeningWindow>, // Weekly opening windows -- no windows means always open
+    pub closed_dates: Vec<i64>,   // Dates the restaurant is closed, as the unix timestamp of the date at 00:00 UTC
+    pub dayparts: Vec<Daypart>,   // Dayparts (breakfast, lunch, dinner...) menu items can be restricted to
+}
+
+impl Space for Schedule {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 4 + 4 + 5 * MAX_OPENING_WINDOWS + 4 + 8 * MAX_CLOSED_DATES + 4 + 4 * MAX_DAYPARTS;
+}
+
+impl Schedule {
+    // always open until the owner sets opening windows
+    pub fn new(restaurant: Pubkey) -> Self {
+        Schedule {
+            version: Self::VERSION,
+            restaurant,
+            utc_offset: 0,
+            windows: Vec::new(),
+            closed_dates: Vec::new(),
+            dayparts: Vec::new(),
+        }
+    }
+
+    pub fn validate(utc_offset: i32, windows: &[OpeningWindow], closed_dates: &[i64], dayparts: &[Daypart]) -> Result<()> {
+        require!(utc_offset.abs() <= 14 * 60 * 60, ProtocolError::InvalidSchedule);
+        require!(windows.len() <= MAX_OPENING_WINDOWS, ProtocolError::InvalidSchedule);
+        require!(closed_dates.len() <= MAX_CLOSED_DATES, ProtocolError::InvalidSchedule);
+        require!(dayparts.len() <= MAX_DAYPARTS, ProtocolError::InvalidSchedule);
+
+        for window in windows {
+            require!(window.weekday < 7, ProtocolError::InvalidSchedule);
+            require!(window.opens_at < 1440 && window.closes_at <= 1440, ProtocolError::InvalidSchedule);
+        }
+
+        for daypart in dayparts {
+            require!(daypart.starts_at < daypart.ends_at && daypart.ends_at <= 1440, ProtocolError::InvalidSchedule);
+        }
+
+        Ok(())
+    }
+
+    // (local day number, weekday with Sunday = 0, minute of the day)
+    fn local_time(&self, now: i64) -> (i64, u8, u16) {
+        let local = now + self.utc_offset as i64;
+        let day = local.div_euclid(86_400);
+
+        // 1970-01-01 was a Thursday
+        (day, ((day + 4) % 7) as u8, (local.rem_euclid(86_400) / 60) as u16)
+    }
+
+    pub fn is_open(&self, now: i64) -> bool {
+        let (day, weekday, minute) = self.local_time(now);
+
+        if self.closed_dates.iter().any(|date| date.div_euclid(86_400) == day) {
+            return false;
+        }
+
+        if self.windows.is_empty() {
+            return true;
+        }
+
+        // a window closing before it opens runs past midnight into the next day
+        self.windows.iter().any(|window| {
+            if window.opens_at < window.closes_at {
+                window.weekday == weekday && window.opens_at <= minute && minute < window.closes_at
+            } else {
+                (window.weekday == weekday && window.opens_at <= minute)
+                    || ((window.weekday + 1) % 7 == weekday && minute < window.closes_at)
+            }
+        })
+    }
+
+    pub fn serves(&self, dayparts: u8, now: i64) -> bool {
+        if dayparts == 0 {
+            return true;
+        }
+
+        let (_, _, minute) = self.local_time(now);
+
+        self.dayparts.iter().enumerate().any(|(i, daypart)| {
+            dayparts & (1 << i) != 0 && daypart.starts_at <= minute && minute < daypart.ends_at
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OpeningWindow {
+    pub weekday: u8,              // Local weekday (0: Sunday .. 6: Saturday)
+    pub opens_at: u16,            // Local minute of the day the restaurant opens
+    pub closes_at: u16,           // Local minute of the day the restaurant closes (exclusive), before opens_at runs past midnight
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Daypart {
+    pub starts_at: u16,           // Local minute of the day the daypart starts
+    pub ends_at: u16,             // Local minute of the day the daypart ends (exclusive)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CategoryMultiplier {
+    pub category: Pubkey,         // MenuItem.category the multiplier applies to
+    pub multiplier_bps: u16,      // 10_000 = 1x
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CategoryTaxRate {
+    pub category: Pubkey,         // MenuItem.category the rate applies to
+    pub rate_bps: u16,            // 10_000 = 100%
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RecipeLine {
+    pub inventory: Pubkey,        // InventoryItem PDA (inventory_state) the ingredient is taken from
+    pub quantity: Quantity,       // Stock used per unit of the menu item, in the unit of the inventory item
+}
+
+impl RecipeLine {
+    pub const SPACE: usize = 32 + Quantity::SPACE;
+}
+
+// Amount in base units of a currency with `decimals` decimals, prices are rescaled to the decimals of the
+// restaurant currency when an order is placed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Money {
+    pub amount: u64,              // Base units -- 10^decimals per currency unit
+    pub decimals: u8,             // Decimals of the currency the amount is expressed in
+}
+
+impl Money {
+    pub const SPACE: usize = 8 + 1;
+
+    pub fn zero(decimals: u8) -> Self {
+        Money { amount: 0, decimals }
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Money> {
+        require!(self.decimals == other.decimals, ProtocolError::InvalidMoney);
+        let amount = self.amount.checked_add(other.amount).ok_or(ProtocolError::MoneyOverflow)?;
+        Ok(Money { amount, decimals: self.decimals })
+    }
+
+    // share of the amount in basis points, rounded to the nearest base unit
+    pub fn bps(self, bps: u16) -> Result<Money> {
+        let amount = (self.amount as u128 * bps as u128 + 5_000) / 10_000;
+        let amount = u64::try_from(amount).map_err(|_| ProtocolError::MoneyOverflow)?;
+        Ok(Money { amount, decimals: self.decimals })
+    }
+
+    // fails instead of dropping the digits the currency can't represent
+    pub fn rescale(self, decimals: u8) -> Result<Money> {
+        let amount = if decimals >= self.decimals {
+            let factor = 10u64.checked_pow((decimals - self.decimals) as u32).ok_or(ProtocolError::MoneyOverflow)?;
+            self.amount.checked_mul(factor).ok_or(ProtocolError::MoneyOverflow)?
+        } else {
+            let factor = 10u64.checked_pow((self.decimals - decimals) as u32).ok_or(ProtocolError::MoneyOverflow)?;
+            let dropped = self.amount % factor;
+            require!(dropped == 0, ProtocolError::InvalidMoney);
+            self.amount / factor
+        };
+        Ok(Money { amount, decimals })
+    }
+
+    // float prices were in currency units, they are kept to the micro unit
+    fn from_legacy(value: f64) -> Money {
+        Money { amount: (value * 1_000_000.0).round() as u64, decimals: 6 }
+    }
+}
+
+// Amount of stock in a unit of measure, see the UNIT_* constants.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Quantity {
+    pub amount: u64,              // Whole units of measure
+    pub unit: u8,                 // Unit of measure -- each, grams or millilitres
+}
+
+impl Quantity {
+    pub const SPACE: usize = 8 + 1;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            [UNIT_EACH, UNIT_GRAM, UNIT_MILLILITRE].contains(&self.unit),
+            ProtocolError::InvalidUnit
+        );
+        Ok(())
+    }
+
+    pub fn checked_add(self, other: Quantity) -> Result<Quantity> {
+        require!(self.unit == other.unit, ProtocolError::UnitMismatch);
+        let amount = self.amount.checked_add(other.amount).ok_or(ProtocolError::QuantityOverflow)?;
+        Ok(Quantity { amount, unit: self.unit })
+    }
+
+    pub fn checked_sub(self, other: Quantity) -> Result<Quantity> {
+        require!(self.unit == other.unit, ProtocolError::UnitMismatch);
+        let amount = self.amount.checked_sub(other.amount).ok_or(ProtocolError::QuantityOverflow)?;
+        Ok(Quantity { amount, unit: self.unit })
+    }
+
+    // float stock had no unit, fractions of a unit are rounded
+    fn from_legacy(value: f64) -> Quantity {
+        Quantity { amount: value.max(0.0).round() as u64, unit: UNIT_EACH }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Promotion {
+    pub starts_at: i64,           // Unix timestamp the promotion starts at
+    pub ends_at: i64,             // Unix timestamp the promotion ends at (exclusive)
+    pub weekday: Option<u8>,      // Only active on this UTC weekday (0: Sunday .. 6: Saturday) -- "double points Tuesday"
+    pub multiplier_bps: u16,      // 10_000 = 1x
+}
+
+// v0 (unversioned) only had `locked`, the bootstrap multisig becomes the only authority.
+impl Versioned for Protocol {
+    const VERSION: u8 = 1;
+
+    fn is_unversioned(data: &[u8]) -> bool {
+        data.len() == ProtocolV0::SPACE
+    }
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!(version == 0, ProtocolError::UnsupportedVersion);
+        let legacy = ProtocolV0::deserialize(&mut &data[8..])?;
+
+        Ok(Protocol {
+            version: Self::VERSION,
+            locked: legacy.locked,
+            authorities: vec![multisig_wallet::id()],
+            threshold: 1,
+            proposal_count: 0,
+            admin_cooldown: DEFAULT_ADMIN_COOLDOWN,
+            fee_bps: 0,
+            paused: 0,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct ProtocolV0 {
+    locked: bool,
+}
+
+impl ProtocolV0 {
+    const SPACE: usize = 8 + 1;
+}
+
+impl Versioned for Proposal {
+    const VERSION: u8 = 1;
+}
+
+impl Versioned for CurrencyListing {
+    const VERSION: u8 = 1;
+}
+
+// v0 (unversioned) had the same fields.
+impl Versioned for Admin {
+    const VERSION: u8 = 1;
+
+    fn is_unversioned(data: &[u8]) -> bool {
+        data.len() == AdminV0::SPACE
+    }
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!(version == 0, ProtocolError::UnsupportedVersion);
+        let legacy = AdminV0::deserialize(&mut &data[8..])?;
+
+        Ok(Admin {
+            version: Self::VERSION,
+            publickey: legacy.publickey,
+            username: legacy.username,
+            initialized: legacy.initialized,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct AdminV0 {
+    publickey: Pubkey,
+    username: String,
+    initialized: i64,
+}
+
+impl AdminV0 {
+    const SPACE: usize = 8 + 32 + 4 + 8 + 5;
+}
+
+// v2 added `pending_orders`, which can't be recovered from a v0 (unversioned) or v1 account so those have to be recreated.
+// v3 added `seed` and `pending_owner`, a v2 restaurant never changed owner so its seed is the owner.
+// v4 added `brand`, older restaurants are standalone.
+impl Versioned for Restaurant {
+    const VERSION: u8 = 4;
+
+    // unversioned restaurants were sized for their strings
+    fn is_unversioned(data: &[u8]) -> bool {
+        data.get(8..)
+            .and_then(|mut reader| RestaurantV0::deserialize(&mut reader).ok())
+            .is_some_and(|legacy| data.len() == RestaurantV0::SPACE + legacy.name.len() + legacy.symbol.len() + legacy.url.len())
+    }
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        let mut reader = &data[8..];
+        let legacy = RestaurantV2::deserialize(&mut reader)?;
+        let (seed, pending_owner) = match version {
+            2 => (legacy.owner, None),
+            3 => (Pubkey::deserialize(&mut reader)?, Option::<Pubkey>::deserialize(&mut reader)?),
+            _ => return err!(ProtocolError::UnsupportedVersion),
+        };
+
+        Ok(Restaurant {
+            version: Self::VERSION,
+            reference: legacy.reference,
+            name: legacy.name,
+            symbol: legacy.symbol,
+            owner: legacy.owner,
+            currency: legacy.currency,
+            url: legacy.url,
+            customer_count: legacy.customer_count,
+            protocol_fee_bps: legacy.protocol_fee_bps,
+            paused: legacy.paused,
+            pending_orders: legacy.pending_orders,
+            seed,
+            pending_owner,
+            brand: None,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct RestaurantV0 {
+    _reference: Pubkey,
+    name: String,
+    symbol: String,
+    _owner: Pubkey,
+    _currency: Pubkey,
+    url: String,
+    _customer_count: u32,
+}
+
+// without the bytes of `name`, `symbol` and `url`
+impl RestaurantV0 {
+    const SPACE: usize = 8 + 32 + 4 + 32 + 4 + 32 + 4 + 32 + 32 + 4 + 4 + 54 + 4 + 4;
+}
+
+#[derive(AnchorDeserialize)]
+struct RestaurantV2 {
+    _version: u8,
+    reference: Pubkey,
+    name: String,
+    symbol: String,
+    owner: Pubkey,
+    currency: Pubkey,
+    url: String,
+    customer_count: u32,
+    protocol_fee_bps: Option<u16>,
+    paused: bool,
+    pending_orders: u32,
+}
+
+// v2 added `customer_count`, memberships issued before were counted by their location.
+impl Versioned for Brand {
+    const VERSION: u8 = 2;
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!(version == 1, ProtocolError::UnsupportedVersion);
+        let legacy = BrandV1::deserialize(&mut &data[8..])?;
+
+        Ok(Brand {
+            version: Self::VERSION,
+            owner: legacy.owner,
+            name: legacy.name,
+            location_count: legacy.location_count,
+            customer_count: 0,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct BrandV1 {
+    _version: u8,
+    owner: Pubkey,
+    name: String,
+    location_count: u32,
+}
+
+// v2 added `hourly_wage`, older staff members have no wage until it is set.
+// v3 added `tip_points`, older staff members get no points until they are set.
+// v4 added `role`, older staff members have no role.
+// v5 added `status`, `status_changed_at` and `status_reason`, older staff members are active since they were added.
+impl Versioned for StaffMember {
+    const VERSION: u8 = 5;
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!((1..Self::VERSION).contains(&version), ProtocolError::UnsupportedVersion);
+
+        // every version appended its fields to the previous layout
+        let mut reader = &data[8..];
+        let legacy = StaffMemberV1::deserialize(&mut reader)?;
+        let hourly_wage = if version >= 2 { u64::deserialize(&mut reader)? } else { 0 };
+        let tip_points = if version >= 3 { u16::deserialize(&mut reader)? } else { 0 };
+        let role = if version >= 4 { u8::deserialize(&mut reader)? } else { ROLE_NONE };
+
+        Ok(StaffMember {
+            version: Self::VERSION,
+            publickey: legacy.publickey,
+            restaurant: legacy.restaurant,
+            username: legacy.username,
+            permissions: legacy.permissions,
+            initialized: legacy.initialized,
+            hourly_wage,
+            tip_points,
+            role,
+            status: STAFF_ACTIVE,
+            status_changed_at: legacy.initialized,
+            status_reason: 0,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct StaffMemberV1 {
+    _version: u8,
+    publickey: Pubkey,
+    restaurant: Pubkey,
+    username: String,
+    permissions: u16,
+    initialized: i64,
+}
+
+// v2 added `hourly_wage` and `tip_points`, a v1 tombstone can't pay out the shifts left unpaid.
+impl Versioned for StaffTombstone {
+    const VERSION: u8 = 2;
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!(version == 1, ProtocolError::UnsupportedVersion);
+        let legacy = StaffTombstoneV1::deserialize(&mut &data[8..])?;
+
+        Ok(StaffTombstone {
+            version: Self::VERSION,
+            restaurant: legacy.restaurant,
+            member: legacy.member,
+            hired_at: legacy.hired_at,
+            terminated_at: legacy.terminated_at,
+            reason: legacy.reason,
+            terminations: legacy.terminations,
+            hourly_wage: 0,
+            tip_points: 0,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct StaffTombstoneV1 {
+    _version: u8,
+    restaurant: Pubkey,
+    member: Pubkey,
+    hired_at: i64,
+    terminated_at: i64,
+    reason: u8,
+    terminations: u16,
+}
+
+// v2 added `paid`, older shifts were never paid.
+impl Versioned for Shift {
+    const VERSION: u8 = 2;
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!(version == 1, ProtocolError::UnsupportedVersion);
+        let legacy = ShiftV1::deserialize(&mut &data[8..])?;
+
+        Ok(Shift {
+            version: Self::VERSION,
+            restaurant: legacy.restaurant,
+            member: legacy.member,
+            shift_id: legacy.shift_id,
+            clock_in: legacy.clock_in,
+            clock_out: legacy.clock_out,
+            corrected_by: legacy.corrected_by,
+            paid: false,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct ShiftV1 {
+    _version: u8,
+    restaurant: Pubkey,
+    member: Pubkey,
+    shift_id: u64,
+    clock_in: i64,
+    clock_out: i64,
+    corrected_by: Option<Pubkey>,
+}
+
+// v2 added `period_tips` and `last_period_tips`, no tips were paid out before.
+// v3 added `tip_period`, `tip_first_shift` and `tip_points`.
+// v4 replaced `tip_first_shift` with the tip time clocked out, the staff member's shifts count towards the tips
+// from its next clock out on.
+impl Versioned for Timesheet {
+    const VERSION: u8 = 4;
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!((1..Self::VERSION).contains(&version), ProtocolError::UnsupportedVersion);
+
+        let mut data = &data[8..];
+        let legacy = TimesheetV1::deserialize(&mut data)?;
+        // v2 appended the tips of both pay periods
+        let (period_tips, last_period_tips) = if version >= 2 {
+            <(u64, u64)>::deserialize(&mut data)?
+        } else {
+            (0, 0)
+        };
+        // v3 appended the tip period the staff member joined at its clock in
+        let tip_points = if version == 3 {
+            <(i64, u64, u16)>::deserialize(&mut data)?.2
+        } else {
+            0
+        };
+
+        Ok(Timesheet {
+            version: Self::VERSION,
+            restaurant: legacy.restaurant,
+            member: legacy.member,
+            shift_count: legacy.shift_count,
+            open_shift: legacy.open_shift,
+            period_start: legacy.period_start,
+            period_seconds: legacy.period_seconds,
+            period_shifts: legacy.period_shifts,
+            last_period_start: legacy.last_period_start,
+            last_period_end: legacy.last_period_end,
+            last_period_seconds: legacy.last_period_seconds,
+            last_period_shifts: legacy.last_period_shifts,
+            period_tips,
+            last_period_tips,
+            tip_period: 0,
+            tip_seconds: 0,
+            tip_points,
+            tip_weight: 0,
+            unpaid_tip_period: 0,
+            unpaid_tip_seconds: 0,
+            unpaid_tip_weight: 0,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct TimesheetV1 {
+    _version: u8,
+    restaurant: Pubkey,
+    member: Pubkey,
+    shift_count: u64,
+    open_shift: Option<Pubkey>,
+    period_start: i64,
+    period_seconds: u64,
+    period_shifts: u32,
+    last_period_start: i64,
+    last_period_end: i64,
+    last_period_seconds: u64,
+    last_period_shifts: u32,
+}
+
+// v0 (unversioned) items never fit the space they were created with, so none exist.
+// v2 added `restaurant`, which can't be recovered from a v1 account so those have to be recreated.
+// v3 replaced the float `price` and `stock` with Money and Quantity.
+// v4 added `reorder_point` and `par`, older items are untracked.
+impl Versioned for InventoryItem {
+    const VERSION: u8 = 4;
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!((2..Self::VERSION).contains(&version), ProtocolError::UnsupportedVersion);
+
+        let (restaurant, sku, category, name, price, stock, last_order) = if version == 2 {
+            let legacy = InventoryItemV2::deserialize(&mut &data[8..])?;
+            let stock = Quantity::from_legacy(legacy.stock);
+            (legacy.restaurant, legacy.sku, legacy.category, legacy.name, Money::from_legacy(legacy.price), stock, legacy.last_order)
+        } else {
+            let legacy = InventoryItemV3::deserialize(&mut &data[8..])?;
+            (legacy.restaurant, legacy.sku, legacy.category, legacy.name, legacy.price, legacy.stock, legacy.last_order)
+        };
+
+        Ok(InventoryItem {
+            version: Self::VERSION,
+            restaurant,
+            sku,
+            category,
+            name,
+            price,
+            stock,
+            last_order,
+            reorder_point: Quantity { amount: 0, unit: stock.unit },
+            par: Quantity { amount: 0, unit: stock.unit },
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct InventoryItemV3 {
+    _version: u8,
+    restaurant: Pubkey,
+    sku: u64,
+    category: Pubkey,
+    name: String,
+    price: Money,
+    stock: Quantity,
+    last_order: u64,
+}
+
+#[derive(AnchorDeserialize)]
+struct InventoryItemV2 {
+    _version: u8,
+    restaurant: Pubkey,
+    sku: u64,
+    category: Pubkey,
+    name: String,
+    price: f64,
+    stock: f64,
+    last_order: u64,
+}
+
+// v2 added `restaurant`, which can't be recovered from a v0 (unversioned) or v1 account so those have to be recreated.
+// v3 added `dayparts`, older items are served all day.
+// v4 replaced the free-form `ingredients` with a `recipe`, the names can't be mapped to inventory items so
+// upgraded items start without one.
+// v5 replaced the float `price` and recipe quantities with Money and Quantity.
+impl Versioned for MenuItem {
+    const VERSION: u8 = 5;
+
+    fn is_unversioned(data: &[u8]) -> bool {
+        data.len() == MenuItemV0::SPACE
+    }
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!((2..Self::VERSION).contains(&version), ProtocolError::UnsupportedVersion);
+
+        if version == 4 {
+            let legacy = MenuItemV4::deserialize(&mut &data[8..])?;
+
+            return Ok(MenuItem {
+                version: Self::VERSION,
+                restaurant: legacy.restaurant,
+                sku: legacy.sku,
+                category: legacy.category,
+                name: legacy.name,
+                price: Money::from_legacy(legacy.price),
+                recipe: legacy.recipe
+                    .into_iter()
+                    .map(|line| RecipeLine { inventory: line.inventory, quantity: Quantity::from_legacy(line.quantity) })
+                    .collect(),
+                active: legacy.active,
+                dayparts: legacy.dayparts,
+            });
+        }
+
+        let mut reader = &data[8..];
+        let legacy = MenuItemV2::deserialize(&mut reader)?;
+        let dayparts = if version >= 3 { u8::deserialize(&mut reader)? } else { 0 };
+
+        Ok(MenuItem {
+            version: Self::VERSION,
+            restaurant: legacy.restaurant,
+            sku: legacy.sku,
+            category: legacy.category,
+            name: legacy.name,
+            price: Money::from_legacy(legacy.price),
+            recipe: Vec::new(),
+            active: legacy.active,
+            dayparts,
+        })
+    }
+}
+
+// only the size of the unversioned layout is needed, its items can't be upgraded
+struct MenuItemV0;
+
+impl MenuItemV0 {
+    const SPACE: usize = 8 + 32 + 4 + 8 + 8 + 8 + 8 + 5;
+}
+
+#[derive(AnchorDeserialize)]
+struct MenuItemV4 {
+    _version: u8,
+    restaurant: Pubkey,
+    sku: u64,
+    category: Pubkey,
+    name: String,
+    price: f64,
+    recipe: Vec<RecipeLineV4>,
+    active: bool,
+    dayparts: u8,
+}
+
+#[derive(AnchorDeserialize)]
+struct RecipeLineV4 {
+    inventory: Pubkey,
+    quantity: f64,
+}
+
+#[derive(AnchorDeserialize)]
+struct MenuItemV2 {
+    _version: u8,
+    restaurant: Pubkey,
+    sku: u64,
+    category: Pubkey,
+    name: String,
+    price: f64,
+    _ingredients: Vec<String>,
+    active: bool,
+}
+
+// v0 (unversioned) customers never fit the space they were created with, so none exist.
+impl Versioned for Customer {
+    const VERSION: u8 = 1;
+}
+
+// v0 (unversioned) memberships were created along with a Customer, so none exist either.
+impl Versioned for CustomerNft {
+    const VERSION: u8 = 1;
+}
+
+// v2 added `restaurant`, which can't be recovered from a v0 (unversioned) or v1 account so those have to be recreated.
+// v3 added `table`, older orders are not bound to a table.
+// v4 added `subtotal` and `tax`, older orders were untaxed so their subtotal is the total.
+// v5 added `escrow`, older orders paid the owner directly so nothing is escrowed.
+// v6 added `tip`, older orders were not tipped.
+// v7 replaced the float `total`, `subtotal` and `tax` with Money.
+// v8 added `points`, older orders were credited when they were placed.
+// v9 added `stock`, the stock deducted by older orders was not recorded.
+impl Versioned for CustomerOrder {
+    const VERSION: u8 = 9;
+
+    fn is_unversioned(data: &[u8]) -> bool {
+        data.len() == CustomerOrderV0::SPACE
+    }
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!((2..Self::VERSION).contains(&version), ProtocolError::UnsupportedVersion);
+
+        // the stock deducted by older orders wasn't recorded, cancelling them puts nothing back
+        if version >= 7 {
+            let mut reader = &data[8..];
+            let legacy = CustomerOrderV7::deserialize(&mut reader)?;
+            let points = if version == 8 { u64::deserialize(&mut reader)? } else { 0 };
+
+            return Ok(CustomerOrder {
+                version: Self::VERSION,
+                order_id: legacy.order_id,
+                restaurant: legacy.restaurant,
+                customer: legacy.customer,
+                items: legacy.items,
+                total: legacy.total,
+                status: legacy.status,
+                created_at: legacy.created_at,
+                updated_at: legacy.updated_at,
+                table: legacy.table,
+                subtotal: legacy.subtotal,
+                tax: legacy.tax,
+                escrow: legacy.escrow,
+                tip: legacy.tip,
+                points,
+                stock: Vec::new(),
+            });
+        }
+
+        // every version appended its fields to the previous layout
+        let mut reader = &data[8..];
+        let legacy = CustomerOrderV2::deserialize(&mut reader)?;
+        let table = if version >= 3 { Option::<Pubkey>::deserialize(&mut reader)? } else { None };
+        let (subtotal, tax) = if version >= 4 {
+            (f32::deserialize(&mut reader)?, f32::deserialize(&mut reader)?)
+        } else {
+            (legacy.total, 0.0)
+        };
+        let escrow = if version >= 5 { u64::deserialize(&mut reader)? } else { 0 };
+        let tip = if version >= 6 { u64::deserialize(&mut reader)? } else { 0 };
+
+        Ok(CustomerOrder {
+            version: Self::VERSION,
+            order_id: legacy.order_id,
+            restaurant: legacy.restaurant,
+            customer: legacy.customer,
+            items: legacy.items,
+            total: Money::from_legacy(legacy.total as f64),
+            status: legacy.status,
+            created_at: legacy.created_at,
+            updated_at: legacy.updated_at,
+            table,
+            subtotal: Money::from_legacy(subtotal as f64),
+            tax: Money::from_legacy(tax as f64),
+            escrow,
+            tip,
+            points: 0,
+            stock: Vec::new(),
+        })
+    }
+}
+
+// only the size of the unversioned layout is needed, its orders can't be upgraded
+struct CustomerOrderV0;
+
+impl CustomerOrderV0 {
+    const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1 + 8 + 8 + 5;
+}
+
+#[derive(AnchorDeserialize)]
+struct CustomerOrderV7 {
+    _version: u8,
+    order_id: u64,
+    restaurant: Pubkey,
+    customer: Pubkey,
+    items: Vec<u64>,
+    total: Money,
+    status: u8,
+    created_at: i64,
+    updated_at: i64,
+    table: Option<Pubkey>,
+    subtotal: Money,
+    tax: Money,
+    escrow: u64,
+    tip: u64,
+}
+
+#[derive(AnchorDeserialize)]
+struct CustomerOrderV2 {
+    _version: u8,
+    order_id: u64,
+    restaurant: Pubkey,
+    customer: Pubkey,
+    items: Vec<u64>,
+    total: f32,
+    status: u8,
+    created_at: i64,
+    updated_at: i64,
+}
+
+impl Versioned for Table {
+    const VERSION: u8 = 1;
+}
+
+impl Versioned for LoyaltyConfig {
+    const VERSION: u8 = 1;
+}
+
+impl Versioned for Schedule {
+    const VERSION: u8 = 1;
+}
+
+impl Versioned for TaxConfig {
+    const VERSION: u8 = 1;
+}
+
+impl Versioned for TaxLedger {
+    const VERSION: u8 = 1;
+}
+
+impl Versioned for LowStockIndex {
+    const VERSION: u8 = 1;
+}
+
+impl Versioned for Vault {
+    const VERSION: u8 = 1;
+}
+
+// v2 added `period_members`.
+// v3 added the tip time clocked out and the payout of a closed period, the staff member's shifts count from its
+// next clock out on.
+impl Versioned for TipPool {
+    const VERSION: u8 = 3;
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!((1..Self::VERSION).contains(&version), ProtocolError::UnsupportedVersion);
+        // v2 only appended `period_members`, which counted the staff that joined at their clock in
+        let legacy = TipPoolV1::deserialize(&mut &data[8..])?;
+
+        Ok(TipPool {
+            version: Self::VERSION,
+            restaurant: legacy.restaurant,
+            vault: legacy.vault,
+            split: legacy.split,
+            balance: legacy.balance,
+            period_start: legacy.period_start,
+            distributed: legacy.distributed,
+            period_members: 0,
+            period_seconds: 0,
+            period_weight: 0,
+            payout_start: 0,
+            payout_end: 0,
+            payout_amount: 0,
+            payout_weight: 0,
+            payout_by_points: false,
+            payout_members: 0,
+            payout_paid: 0,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct TipPoolV1 {
+    _version: u8,
+    restaurant: Pubkey,
+    vault: Pubkey,
+    split: u8,
+    balance: u64,
+    period_start: i64,
+    distributed: u64,
+}
+
+// v0 (unversioned) had the same fields.
+impl Versioned for Reward {
+    const VERSION: u8 = 1;
+
+    fn is_unversioned(data: &[u8]) -> bool {
+        data.len() == RewardV0::SPACE
+    }
+
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        require!(version == 0, ProtocolError::UnsupportedVersion);
+        let legacy = RewardV0::deserialize(&mut &data[8..])?;
+
+        Ok(Reward {
+            version: Self::VERSION,
+            category: legacy.category,
+            restaurant: legacy.restaurant,
+            reward_points: legacy.reward_points,
+            reward_item: legacy.reward_item,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct RewardV0 {
+    category: Pubkey,
+    restaurant: Pubkey,
+    reward_points: u64,
+    reward_item: Pubkey,
+}
+
+impl RewardV0 {
+    const SPACE: usize = 8 + 32 + 4 + 32 + 4 + 8 + 32 + 4 + 5;
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct Attributes {
     pub key: String,
     pub value: String,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Monday 2024-01-01 00:00 UTC
+    const MONDAY: i64 = 1_704_067_200;
+
+    fn money(amount: u64, decimals: u8) -> Money {
+        Money { amount, decimals }
+    }
+
+    fn window(weekday: u8, opens_at: u16, closes_at: u16) -> OpeningWindow {
+        OpeningWindow { weekday, opens_at, closes_at }
+    }
+
+    fn schedule(utc_offset: i32, windows: Vec<OpeningWindow>, closed_dates: Vec<i64>) -> Schedule {
+        Schedule { utc_offset, windows, closed_dates, ..Schedule::new(Pubkey::default()) }
+    }
+
+    fn loyalty(points_per_unit: u64, category_multipliers: Vec<CategoryMultiplier>, promotions: Vec<Promotion>) -> LoyaltyConfig {
+        LoyaltyConfig { points_per_unit, category_multipliers, promotions, ..LoyaltyConfig::new(Pubkey::default()) }
+    }
+
+    #[test]
+    fn rescale_keeps_the_value() {
+        assert_eq!(money(499, 2).rescale(6).unwrap(), money(4_990_000, 6));
+        assert_eq!(money(4_990_000, 6).rescale(2).unwrap(), money(499, 2));
+        assert_eq!(money(499, 2).rescale(2).unwrap(), money(499, 2));
+    }
+
+    #[test]
+    fn rescale_rejects_dropped_digits() {
+        assert_eq!(money(4_999_999, 6).rescale(2).unwrap_err(), ProtocolError::InvalidMoney.into());
+    }
+
+    #[test]
+    fn rescale_overflow() {
+        assert_eq!(money(u64::MAX, 0).rescale(1).unwrap_err(), ProtocolError::MoneyOverflow.into());
+        assert_eq!(money(1, 0).rescale(20).unwrap_err(), ProtocolError::MoneyOverflow.into());
+    }
+
+    #[test]
+    fn bps_rounds_to_nearest() {
+        assert_eq!(money(15, 2).bps(1_000).unwrap(), money(2, 2));
+        assert_eq!(money(14, 2).bps(1_000).unwrap(), money(1, 2));
+        assert_eq!(money(1_000, 2).bps(825).unwrap(), money(83, 2));
+        assert_eq!(money(u64::MAX, 0).bps(10_000).unwrap(), money(u64::MAX, 0));
+        assert_eq!(money(u64::MAX, 0).bps(10_001).unwrap_err(), ProtocolError::MoneyOverflow.into());
+    }
+
+    #[test]
+    fn money_checked_add() {
+        assert_eq!(money(1, 2).checked_add(money(2, 2)).unwrap(), money(3, 2));
+        assert_eq!(money(1, 2).checked_add(money(2, 6)).unwrap_err(), ProtocolError::InvalidMoney.into());
+        assert_eq!(money(u64::MAX, 2).checked_add(money(1, 2)).unwrap_err(), ProtocolError::MoneyOverflow.into());
+    }
+
+    #[test]
+    fn legacy_floats_convert() {
+        assert_eq!(Money::from_legacy(4.99), money(4_990_000, 6));
+        assert_eq!(Money::from_legacy(0.1 + 0.2), money(300_000, 6));
+
+        assert_eq!(Quantity::from_legacy(2.5), Quantity { amount: 3, unit: UNIT_EACH });
+        assert_eq!(Quantity::from_legacy(-1.0), Quantity { amount: 0, unit: UNIT_EACH });
+    }
+
+    #[test]
+    fn quantity_checked_math() {
+        let grams = |amount| Quantity { amount, unit: UNIT_GRAM };
+
+        assert_eq!(grams(5).checked_add(grams(3)).unwrap(), grams(8));
+        assert_eq!(grams(5).checked_sub(grams(5)).unwrap(), grams(0));
+        assert_eq!(grams(5).checked_sub(grams(6)).unwrap_err(), ProtocolError::QuantityOverflow.into());
+        assert_eq!(grams(u64::MAX).checked_add(grams(1)).unwrap_err(), ProtocolError::QuantityOverflow.into());
+        assert_eq!(
+            grams(5).checked_add(Quantity { amount: 1, unit: UNIT_EACH }).unwrap_err(),
+            ProtocolError::UnitMismatch.into()
+        );
+        assert_eq!(Quantity { amount: 1, unit: 3 }.validate().unwrap_err(), ProtocolError::InvalidUnit.into());
+    }
+
+    #[test]
+    fn schedule_without_windows_is_always_open() {
+        assert!(schedule(0, vec![], vec![]).is_open(MONDAY));
+    }
+
+    #[test]
+    fn schedule_windows_use_local_time() {
+        // Monday 09:00 - 17:00 at UTC+2
+        let schedule = schedule(2 * 60 * 60, vec![window(1, 9 * 60, 17 * 60)], vec![]);
+
+        assert!(!schedule.is_open(MONDAY + 6 * 60 * 60 + 59 * 60));
+        assert!(schedule.is_open(MONDAY + 7 * 60 * 60));
+        assert!(schedule.is_open(MONDAY + 14 * 60 * 60 + 59 * 60));
+        assert!(!schedule.is_open(MONDAY + 15 * 60 * 60));
+    }
+
+    #[test]
+    fn schedule_overnight_window() {
+        // Saturday 22:00 - Sunday 02:00
+        let schedule = schedule(0, vec![window(6, 22 * 60, 2 * 60)], vec![]);
+        let saturday = MONDAY - 2 * 86_400;
+
+        assert!(!schedule.is_open(saturday + 21 * 60 * 60));
+        assert!(schedule.is_open(saturday + 23 * 60 * 60));
+        assert!(schedule.is_open(saturday + 86_400 + 60 * 60));
+        assert!(!schedule.is_open(saturday + 86_400 + 2 * 60 * 60));
+        // the window only runs into the day after its weekday
+        assert!(!schedule.is_open(MONDAY + 60 * 60));
+    }
+
+    #[test]
+    fn schedule_closed_dates() {
+        let schedule = schedule(0, vec![window(1, 0, 1440)], vec![MONDAY]);
+
+        assert!(!schedule.is_open(MONDAY + 12 * 60 * 60));
+        assert!(schedule.is_open(MONDAY + 7 * 86_400 + 12 * 60 * 60));
+    }
+
+    #[test]
+    fn points_per_whole_unit() {
+        let config = loyalty(10, vec![], vec![]);
+
+        assert_eq!(config.points(money(1_250, 2), &[], MONDAY).unwrap(), 125);
+        assert_eq!(config.points(money(99, 2), &[], MONDAY).unwrap(), 9);
+    }
+
+    #[test]
+    fn points_below_min_spend() {
+        let config = LoyaltyConfig { min_spend: 1_000, ..loyalty(10, vec![], vec![]) };
+
+        assert_eq!(config.points(money(999, 2), &[], MONDAY).unwrap(), 0);
+        assert_eq!(config.points(money(1_000, 2), &[], MONDAY).unwrap(), 100);
+    }
+
+    #[test]
+    fn points_weight_category_multipliers_by_price() {
+        let drinks = Pubkey::new_unique();
+        let food = Pubkey::new_unique();
+        let config = loyalty(1, vec![CategoryMultiplier { category: drinks, multiplier_bps: 20_000 }], vec![]);
+
+        // 3.00 of drinks at 2x and 1.00 of food at 1x earn 1.75x on the 4.00 paid
+        let lines = [(drinks, money(300, 2)), (food, money(100, 2))];
+        assert_eq!(config.points(money(400, 2), &lines, MONDAY).unwrap(), 7);
+    }
+
+    #[test]
+    fn points_apply_the_highest_active_promotion() {
+        let promotion = |weekday, multiplier_bps| Promotion {
+            starts_at: MONDAY - 86_400,
+            ends_at: MONDAY + 7 * 86_400,
+            weekday,
+            multiplier_bps,
+        };
+        let config = loyalty(1, vec![], vec![promotion(None, 15_000), promotion(Some(2), 30_000)]);
+
+        assert_eq!(config.points(money(1_000, 2), &[], MONDAY).unwrap(), 15);
+        assert_eq!(config.points(money(1_000, 2), &[], MONDAY + 86_400).unwrap(), 30);
+        assert_eq!(config.points(money(1_000, 2), &[], MONDAY + 7 * 86_400).unwrap(), 10);
+    }
+
+    #[test]
+    fn points_overflow() {
+        let config = loyalty(u64::MAX, vec![], vec![]);
+
+        assert_eq!(
+            config.points(money(u64::MAX, 0), &[], MONDAY).unwrap_err(),
+            ProtocolError::InvalidLoyaltyConfig.into()
+        );
+    }
+
+    #[test]
+    fn unversioned_protocol_upgrades() {
+        let mut data = Protocol::DISCRIMINATOR.to_vec();
+        data.push(1);
+
+        assert!(Protocol::is_unversioned(&data));
+        let protocol = Protocol::upgrade(0, &data).unwrap();
+        assert!(protocol.locked);
+        assert_eq!(protocol.authorities, vec![multisig_wallet::id()]);
+        assert_eq!(protocol.threshold, 1);
+    }
+
+    #[test]
+    fn unversioned_restaurant_is_told_apart_by_size() {
+        let mut data = Restaurant::DISCRIMINATOR.to_vec();
+        data.extend([2; 32]);
+        ("Diner".to_string(), "DNR".to_string(), [0u8; 64], "https://diner.xyz".to_string(), 0u32)
+            .serialize(&mut data)
+            .unwrap();
+
+        // the first byte of `reference` must not be read as a version
+        data.resize(RestaurantV0::SPACE + 5 + 3 + 17, 0);
+        assert!(Restaurant::is_unversioned(&data));
+        assert!(Restaurant::upgrade(0, &data).is_err());
+
+        data.push(0);
+        assert!(!Restaurant::is_unversioned(&data));
+    }
+
+    #[test]
+    fn timesheet_v3_keeps_its_tips_and_points() {
+        let mut data = Timesheet::DISCRIMINATOR.to_vec();
+        (3u8, Pubkey::default(), Pubkey::default(), 4u64, None::<Pubkey>, MONDAY, 3_600u64, 1u32).serialize(&mut data).unwrap();
+        (0i64, 0i64, 0u64, 0u32, 250u64, 100u64, MONDAY, 2u64, 3u16).serialize(&mut data).unwrap();
+
+        let timesheet = Timesheet::upgrade(3, &data).unwrap();
+        assert_eq!((timesheet.period_tips, timesheet.last_period_tips), (250, 100));
+        assert_eq!(timesheet.tip_points, 3);
+        assert_eq!((timesheet.tip_period, timesheet.tip_seconds), (0, 0));
+    }
+}
@@ -24,7 +24,11 @@ import {
 } from "@solana/web3.js";
 import {
   getTokenMetadata,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
 } from "@solana/spl-token";
+import { expect } from "chai";
 import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync, RawMint } from "@solana/spl-token";
 
 
@@ -57,20 +61,45 @@ describe("restaurant_protocol", () => {
 
   // ACCOUNT ADDRESSES /////////////////////////////////////////////////////////////////////////////////////////////////////////////////
   const protocol = PublicKey.findProgramAddressSync([Buffer.from('protocol')], program.programId)[0];
+  const getProposal = (id: number) => PublicKey.findProgramAddressSync([Buffer.from('proposal'), new anchor.BN(id).toArrayLike(Buffer, "le", 8)], program.programId)[0];
 
   const auth = PublicKey.findProgramAddressSync([Buffer.from('auth')], program.programId)[0];
   const admin_state = PublicKey.findProgramAddressSync([Buffer.from('admin_state'), wallet.publicKey.toBuffer()], program.programId)[0];
   
   const restaurant = PublicKey.findProgramAddressSync([Buffer.from('restaurant'), RESTAURANT_OWNER.toBuffer()], program.programId)[0];
-  const restaurant_admin_state = PublicKey.findProgramAddressSync([Buffer.from('admin'), RESTAURANT_ADMIN.toBuffer(), restaurant.toBuffer()], program.programId)[0];
+  const restaurant_admin_state = PublicKey.findProgramAddressSync([Buffer.from('staff'), RESTAURANT_ADMIN.toBuffer(), restaurant.toBuffer()], program.programId)[0];
   const restaurant_mint = PublicKey.findProgramAddressSync([Buffer.from('mint'), restaurant.toBuffer()], program.programId)[0];
 
-  const employee_state = PublicKey.findProgramAddressSync([Buffer.from('employee'), EMPLOYEE.toBuffer(), restaurant.toBuffer()], program.programId)[0];
+  const employee_state = PublicKey.findProgramAddressSync([Buffer.from('staff'), EMPLOYEE.toBuffer(), restaurant.toBuffer()], program.programId)[0];
 
   const customer_nft = PublicKey.findProgramAddressSync([Buffer.from('nft'), CUSTOMER.toBuffer()], program.programId)[0];
   const customer_nft_mint = PublicKey.findProgramAddressSync([Buffer.from('mint'), CUSTOMER.toBuffer()], program.programId)[0];
 
 
+  // ORDER ACCOUNTS /////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+  const currency_keypair = Keypair.generate();
+  const currency = currency_keypair.publicKey;
+  const currency_listing = PublicKey.findProgramAddressSync([Buffer.from('currency'), currency.toBuffer()], program.programId)[0];
+  const vault = PublicKey.findProgramAddressSync([Buffer.from('vault'), restaurant.toBuffer(), currency.toBuffer()], program.programId)[0];
+  const vault_tokens = PublicKey.findProgramAddressSync([Buffer.from('vault_tokens'), vault.toBuffer()], program.programId)[0];
+  const tip_pool = PublicKey.findProgramAddressSync([Buffer.from('tip_pool'), vault.toBuffer()], program.programId)[0];
+  const loyalty_config = PublicKey.findProgramAddressSync([Buffer.from('loyalty'), restaurant.toBuffer()], program.programId)[0];
+  const schedule = PublicKey.findProgramAddressSync([Buffer.from('schedule'), restaurant.toBuffer()], program.programId)[0];
+  const tax_config = PublicKey.findProgramAddressSync([Buffer.from('tax'), restaurant.toBuffer()], program.programId)[0];
+  const tax_ledger = PublicKey.findProgramAddressSync([Buffer.from('tax_ledger'), restaurant.toBuffer()], program.programId)[0];
+  const low_stock_index = PublicKey.findProgramAddressSync([Buffer.from('low_stock'), restaurant.toBuffer()], program.programId)[0];
+
+  const menu_item = Keypair.generate().publicKey;
+  const menu_state = PublicKey.findProgramAddressSync([Buffer.from('menu_state'), menu_item.toBuffer(), restaurant.toBuffer()], program.programId)[0];
+
+  const buyer_profile = PublicKey.findProgramAddressSync([Buffer.from('customer'), buyer.publicKey.toBuffer(), restaurant.toBuffer()], program.programId)[0];
+  const buyer_nft = PublicKey.findProgramAddressSync([Buffer.from('member_nft'), buyer.publicKey.toBuffer(), restaurant.toBuffer()], program.programId)[0];
+  const buyer_nft_mint = PublicKey.findProgramAddressSync([Buffer.from('mint'), buyer_nft.toBuffer()], program.programId)[0];
+
+  const order = Keypair.generate().publicKey;
+  const order_state = PublicKey.findProgramAddressSync([Buffer.from('order_state'), order.toBuffer(), restaurant.toBuffer()], program.programId)[0];
+
+
   // REFERENCE GROUPS /////////////////////////////////////////////////////////////////////////////////////////////////////////////////
   const RESTAURANT_REFERENCE = new PublicKey('2333')
 
@@ -80,6 +109,17 @@ describe("restaurant_protocol", () => {
   const symbol = "SYM";
   const url = "URL";
   const restaurant_admin_username = "MATT";  // 5 characters MAX
+  const CURRENCY_DECIMALS = 6;
+
+  // MENU DATA /////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+  const menu_sku = new anchor.BN(1);
+  const menu_price = { amount: new anchor.BN(499), decimals: 2 };  // 4.99
+
+  // STAFF PERMISSIONS (see constant.rs) /////////////////////////////////////////////////////////////////////////////////////////
+  const PERMISSION_UPDATE_ORDERS = 1 << 2;
+  const PERMISSION_ALL = (1 << 8) - 1;
+  const ROLE_KITCHEN = 2;
+  const ROLE_MANAGER = 4;
 
   // EMPLOYEE DATA /////////////////////////////////////////////////////////////////////////////////////////////////////////////////
   const employee_username = "MATT";  // 5 characters MAX
@@ -108,7 +148,10 @@ describe("restaurant_protocol", () => {
 
     const transaction = new Transaction().add(
       await program.methods
-      .initializeProtocolAccount()
+      .initializeProtocolAccount(
+        [wallet.publicKey],
+        1
+      )
       .accounts({
         admin: wallet.publicKey,
         protocol: protocol,
@@ -123,12 +166,45 @@ describe("restaurant_protocol", () => {
   it("Protocol lock is toggled!", async () => {
     const transaction = new Transaction().add(
       await program.methods
-      .lockProtocol()
+      .createProposal({ toggleLock: {} })
       .accounts({
-        admin: wallet.publicKey,
+        authority: wallet.publicKey,
+        proposal: getProposal(0),
+        protocol: protocol,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction(),
+      await program.methods
+      .executeProposal()
+      .accounts({
+        authority: wallet.publicKey,
+        proposal: getProposal(0),
+        protocol: protocol,
+      })
+      .instruction()
+    );
+    
+    await sendAndConfirmTransaction(connection, transaction, [wallet.payer], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
+  });
+
+  it("Admin cooldown is disabled for local testing!", async () => {
+    const transaction = new Transaction().add(
+      await program.methods
+      .createProposal({ setAdminCooldown: { cooldown: new anchor.BN(0) } })
+      .accounts({
+        authority: wallet.publicKey,
+        proposal: getProposal(1),
         protocol: protocol,
         systemProgram: SystemProgram.programId,
       })
+      .instruction(),
+      await program.methods
+      .executeProposal()
+      .accounts({
+        authority: wallet.publicKey,
+        proposal: getProposal(1),
+        protocol: protocol,
+      })
       .instruction()
     );
     
@@ -138,36 +214,83 @@ describe("restaurant_protocol", () => {
   it("Initialize Protocol Admin", async () => {
     const username = "MATT";  // 5 characters MAX
 
+    const proposeAdminIx = await program.methods
+      .createProposal({ addAdmin: { admin: wallet.publicKey } })
+      .accounts({
+        authority: wallet.publicKey,
+        proposal: getProposal(2),
+        protocol: protocol,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction()
+
     const createAdminIx = await program.methods
       .initializeAdminAccount(username)
       .accounts({
         admin: wallet.publicKey,
-        adminState: null,
         newAdmin: wallet.publicKey,
         newAdminState: admin_state,
+        proposal: getProposal(2),
         protocol: protocol,
         systemProgram: SystemProgram.programId,
       })
       .instruction()
 
-    const tx = new anchor.web3.Transaction().add(createAdminIx);
+    const tx = new anchor.web3.Transaction().add(proposeAdminIx).add(createAdminIx);
     await sendAndConfirmTransaction(connection, tx, [wallet.payer], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
   });
 
+  it("Currency is listed!", async () => {
+    await createMint(connection, wallet.payer, wallet.publicKey, null, CURRENCY_DECIMALS, currency_keypair);
+
+    const transaction = new Transaction().add(
+      await program.methods
+      .createProposal({ listCurrency: { mint: currency, listed: true } })
+      .accounts({
+        authority: wallet.publicKey,
+        proposal: getProposal(3),
+        protocol: protocol,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction(),
+      await program.methods
+      .listCurrency()
+      .accounts({
+        authority: wallet.publicKey,
+        proposal: getProposal(3),
+        mint: currency,
+        currencyListing: currency_listing,
+        protocol: protocol,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction()
+    );
+
+    await sendAndConfirmTransaction(connection, transaction, [wallet.payer], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
+  });
+
   it("Restaurant is created!", async () => {
     const createRestaurantIx = await program.methods
       .addRestaurant(
         RESTAURANT_REFERENCE,
         name,
         symbol,
+        currency,
         url
       )
       .accounts({
         admin: wallet.publicKey,
-        adminState: null,
+        adminState: admin_state,
         owner: RESTAURANT_OWNER,
         restaurant: restaurant,
+        loyaltyConfig: loyalty_config,
+        schedule: schedule,
+        taxConfig: tax_config,
+        taxLedger: tax_ledger,
+        lowStockIndex: low_stock_index,
         mint: restaurant_mint,
+        auth: auth,
+        currencyListing: currency_listing,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         protocol: protocol,
         token2022Program: TOKEN_2022_PROGRAM_ID,
@@ -179,45 +302,166 @@ describe("restaurant_protocol", () => {
     await sendAndConfirmTransaction(connection, tx, [wallet.payer], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
   });
 
-  it("Admin Added to Restaurant!", async () => {
-    const createEmployeeIx = await program.methods
-      .initializeRestaurantAdmin(
-        restaurant_admin_username
+  it("Manager Added to Restaurant!", async () => {
+    const addStaffIx = await program.methods
+      .addStaffMember(
+        restaurant_admin_username,
+        PERMISSION_ALL,
+        new anchor.BN(0),
+        0,
+        ROLE_MANAGER
       )
       .accounts({
+        authority: RESTAURANT_OWNER,
+        staffMember: null,
         restaurant: restaurant,
-        restaurantOwner: RESTAURANT_OWNER,
-        restaurantAdmin: RESTAURANT_ADMIN,
-        restaurantAdminState: restaurant_admin_state,
+        member: RESTAURANT_ADMIN,
+        memberState: restaurant_admin_state,
         protocol: protocol,
         systemProgram: SystemProgram.programId,
       })
       .instruction()
 
-    const tx = new anchor.web3.Transaction().add(createEmployeeIx);
+    const tx = new anchor.web3.Transaction().add(addStaffIx);
     await sendAndConfirmTransaction(connection, tx, [wallet.payer], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
   });
 
   it("Employee Added to Restaurant!", async () => {
-    const createEmployeeIx = await program.methods
-      .initializeEmployeeAccount(
-        employee_username
+    const addStaffIx = await program.methods
+      .addStaffMember(
+        employee_username,
+        PERMISSION_UPDATE_ORDERS,
+        new anchor.BN(15_000_000),
+        10,
+        ROLE_KITCHEN
       )
       .accounts({
-        restaurantAdmin: RESTAURANT_ADMIN,
-        restaurantAdminState: restaurant_admin_state,
-        employee: EMPLOYEE,
-        employeeState: employee_state,
+        authority: RESTAURANT_ADMIN,
+        staffMember: restaurant_admin_state,
         restaurant: restaurant,
+        member: EMPLOYEE,
+        memberState: employee_state,
         protocol: protocol,
         systemProgram: SystemProgram.programId,
       })
       .instruction()
 
-    const tx = new anchor.web3.Transaction().add(createEmployeeIx);
+    const tx = new anchor.web3.Transaction().add(addStaffIx);
     await sendAndConfirmTransaction(connection, tx, [RESTAURANT_ADMIN], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
   });
 
+  it("Menu item added!", async () => {
+    const addMenuItemIx = await program.methods
+      .addMenuItem(
+        menu_sku,
+        RESTAURANT_REFERENCE,
+        "BURGER",
+        menu_price,
+        [],
+        true
+      )
+      .accounts({
+        authority: RESTAURANT_ADMIN,
+        staffMember: restaurant_admin_state,
+        brand: null,
+        restaurant: restaurant,
+        menuItem: menu_item,
+        menuState: menu_state,
+        protocol: protocol,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction()
+
+    const tx = new anchor.web3.Transaction().add(addMenuItemIx);
+    await sendAndConfirmTransaction(connection, tx, [RESTAURANT_ADMIN], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
+  });
+
+  it("Customer added!", async () => {
+    const addCustomerIx = await program.methods
+      .addCustomer(
+        new anchor.BN(1),
+        url,
+        []
+      )
+      .accounts({
+        authority: RESTAURANT_ADMIN,
+        staffMember: restaurant_admin_state,
+        customer: buyer.publicKey,
+        customerProfile: buyer_profile,
+        customerNft: buyer_nft,
+        mint: buyer_nft_mint,
+        collectionMint: restaurant_mint,
+        auth: auth,
+        customerMintAta: getAssociatedTokenAddressSync(buyer_nft_mint, buyer.publicKey, false, TOKEN_2022_PROGRAM_ID),
+        restaurantOwner: RESTAURANT_OWNER,
+        restaurant: restaurant,
+        brand: null,
+        protocol: protocol,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction()
+
+    const tx = new anchor.web3.Transaction().add(addCustomerIx);
+    await sendAndConfirmTransaction(connection, tx, [RESTAURANT_ADMIN], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
+  });
+
+  it("Customer placed an order!", async () => {
+    const buyer_currency_ata = await getOrCreateAssociatedTokenAccount(connection, wallet.payer, currency, buyer.publicKey);
+    await mintTo(connection, wallet.payer, currency, buyer_currency_ata.address, wallet.payer, 100_000_000);
+
+    const tip = new anchor.BN(1_000_000);
+    const addOrderIx = await program.methods
+      .addOrder(
+        new anchor.BN(1),
+        [menu_sku],
+        tip
+      )
+      .accounts({
+        restaurant: restaurant,
+        currency: currency,
+        currencyListing: currency_listing,
+        customer: buyer.publicKey,
+        customerCurrencyAta: buyer_currency_ata.address,
+        vault: vault,
+        vaultTokens: vault_tokens,
+        tipPool: tip_pool,
+        customerProfile: buyer_profile,
+        customerNft: buyer_nft,
+        loyaltyConfig: loyalty_config,
+        schedule: schedule,
+        taxConfig: tax_config,
+        lowStockIndex: low_stock_index,
+        authority: null,
+        staffMember: null,
+        table: null,
+        order: order,
+        orderState: order_state,
+        protocol: protocol,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      // menu items of the order, then the inventory items of their recipes
+      .remainingAccounts([
+        { pubkey: menu_state, isSigner: false, isWritable: false },
+      ])
+      .instruction()
+
+    const tx = new anchor.web3.Transaction().add(addOrderIx);
+    await sendAndConfirmTransaction(connection, tx, [buyer], {commitment: "finalized", skipPreflight: true}).then(confirm).then(log);
+
+    // the 4.99 menu price is rescaled to the currency decimals, the restaurant is untaxed by default
+    const orderState = await program.account.customerOrder.fetch(order_state);
+    expect(orderState.subtotal.amount.toNumber()).to.equal(4_990_000);
+    expect(orderState.subtotal.decimals).to.equal(CURRENCY_DECIMALS);
+    expect(orderState.tax.amount.toNumber()).to.equal(0);
+    expect(orderState.total.amount.toNumber()).to.equal(4_990_000);
+    expect(orderState.escrow.toNumber()).to.equal(4_990_000 + tip.toNumber());
+    expect(orderState.tip.toNumber()).to.equal(tip.toNumber());
+  });
+
   // it("Employee clocked in!", async () => {
   //   // Add your test here.
   //   const tx = await program.methods.initialize().rpc();